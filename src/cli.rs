@@ -0,0 +1,77 @@
+use crate::verbosity::Verbosity;
+
+const USAGE: &str = "usage: advent-of-code-2023 [-q | -v] [--log <file>] [--bag <color>=<count>,...]";
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Options {
+    pub(crate) verbosity: Verbosity,
+    /// Trace output goes here instead of stderr.
+    pub(crate) log_file: Option<String>,
+    /// Cube counts per colour to check the day 2 games against.
+    pub(crate) bag: Option<Vec<(String, u64)>>,
+}
+
+fn parse_bag(spec: &str) -> Result<Vec<(String, u64)>, String> {
+    let mut bag: Vec<(String, u64)> = Vec::new();
+    for set in spec.split(',') {
+        let Some((color, count)) = set.split_once('=') else {
+            return Err(format!("bag entry {set:?} is not <color>=<count>"));
+        };
+        let count = count.parse::<u64>().map_err(|_| format!("invalid cube count {count:?} for {color}"))?;
+        if color.is_empty() {
+            return Err(format!("bag entry {set:?} has no colour"));
+        }
+        if bag.iter().any(|(seen, _)| seen == color) {
+            return Err(format!("colour {color} appears twice in the bag"));
+        }
+        bag.push((color.to_string(), count));
+    }
+    return Ok(bag);
+}
+
+pub(crate) fn parse(args: impl IntoIterator<Item=String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-q" | "--quiet" => options.verbosity = Verbosity::Quiet,
+            "-v" | "--verbose" => options.verbosity = Verbosity::Trace,
+            "--log" => options.log_file = Some(args.next().ok_or(format!("--log needs a file\n{USAGE}"))?),
+            "--bag" => {
+                let spec = args.next().ok_or(format!("--bag needs a specification\n{USAGE}"))?;
+                options.bag = Some(parse_bag(&spec).map_err(|error| format!("{error}\n{USAGE}"))?);
+            }
+            _ => return Err(format!("unknown argument {arg:?}\n{USAGE}"))
+        }
+    }
+    return Ok(options);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_verbosity_and_log_file() {
+        assert_eq!(parse(args(&[])), Ok(Options::default()));
+        assert_eq!(parse(args(&["-v", "--log", "trace.log"])), Ok(Options { verbosity: Verbosity::Trace, log_file: Some(String::from("trace.log")), bag: None }));
+        assert_eq!(parse(args(&["-v", "-q"])).map(|options| options.verbosity), Ok(Verbosity::Quiet));
+        assert!(parse(args(&["--log"])).is_err());
+        assert!(parse(args(&["--loud"])).is_err());
+    }
+
+    #[test]
+    fn parses_bag() {
+        let bag = vec![(String::from("red"), 12), (String::from("green"), 13), (String::from("blue"), 14)];
+        assert_eq!(parse(args(&["--bag", "red=12,green=13,blue=14"])).map(|options| options.bag), Ok(Some(bag)));
+        assert!(parse(args(&["--bag"])).is_err());
+        assert!(parse(args(&["--bag", "red=12,green"])).is_err());
+        assert!(parse(args(&["--bag", "red=-1"])).is_err());
+        assert!(parse(args(&["--bag", "=3"])).is_err());
+        assert!(parse(args(&["--bag", "red=1,red=2"])).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use super::Digit;
+
+/// Aho-Corasick automaton over a digit vocabulary. Failure links are folded
+/// into a dense byte transition table, so scanning is one lookup per byte.
+pub(super) struct Automaton {
+    transitions: Vec<[u32; 256]>,
+    /// Length and value of every pattern ending in each state.
+    outputs: Vec<Vec<(usize, u32)>>,
+    longest: usize,
+}
+
+impl Automaton {
+    pub(super) fn new(patterns: &[(&str, u32)]) -> Automaton {
+        let patterns: Vec<(&[u8], u32)> = patterns.iter().map(|(pattern, value)| (pattern.as_bytes(), *value)).collect();
+        return Automaton::from_bytes(&patterns);
+    }
+
+    fn from_bytes(patterns: &[(&[u8], u32)]) -> Automaton {
+        let mut children: Vec<[Option<u32>; 256]> = vec![[None; 256]];
+        let mut outputs: Vec<Vec<(usize, u32)>> = vec![Vec::new()];
+        for (pattern, value) in patterns {
+            let mut state = 0;
+            for &byte in pattern.iter() {
+                state = match children[state][byte as usize] {
+                    Some(next) => next as usize,
+                    None => {
+                        children.push([None; 256]);
+                        outputs.push(Vec::new());
+                        children[state][byte as usize] = Some(children.len() as u32 - 1);
+                        children.len() - 1
+                    }
+                };
+            }
+            outputs[state].push((pattern.len(), *value));
+        }
+
+        // Breadth-first, so a state's failure target is complete before its children need it.
+        let mut transitions: Vec<[u32; 256]> = vec![[0; 256]; children.len()];
+        let mut failure: Vec<usize> = vec![0; children.len()];
+        let mut queue: Vec<usize> = vec![0];
+        let mut index = 0;
+        while index < queue.len() {
+            let state = queue[index];
+            index += 1;
+            for byte in 0..256 {
+                match children[state][byte] {
+                    Some(child) => {
+                        let child = child as usize;
+                        if state != 0 {
+                            failure[child] = transitions[failure[state]][byte] as usize;
+                            let inherited = outputs[failure[child]].clone();
+                            outputs[child].extend(inherited);
+                        }
+                        transitions[state][byte] = child as u32;
+                        queue.push(child);
+                    }
+                    None => transitions[state][byte] = if state == 0 { 0 } else { transitions[failure[state]][byte] },
+                }
+            }
+        }
+        let longest = patterns.iter().map(|(pattern, _)| pattern.len()).max().unwrap_or(0);
+        return Automaton { transitions, outputs, longest };
+    }
+
+    /// Every match in `text`, ordered by the position it ends at.
+    pub(super) fn matches<'a>(&'a self, text: &'a str) -> impl Iterator<Item=Digit> + 'a {
+        return self.scan(text.bytes());
+    }
+
+    fn scan<'a>(&'a self, bytes: impl Iterator<Item=u8> + 'a) -> impl Iterator<Item=Digit> + 'a {
+        let mut state = 0;
+        return bytes.enumerate().flat_map(move |(index, byte)| {
+            state = self.transitions[state][byte as usize] as usize;
+            self.outputs[state].iter().map(move |&(length, value)| Digit { position: index + 1 - length, length, value })
+        });
+    }
+
+    /// Earliest starting match in `bytes`, longest on ties. Stops as soon as no
+    /// pattern could still start at or before it.
+    fn earliest(&self, bytes: impl Iterator<Item=u8>) -> Option<Digit> {
+        let mut earliest: Option<Digit> = None;
+        for digit in self.scan(bytes) {
+            if earliest.is_some_and(|earliest| digit.position + digit.length > earliest.position + self.longest) {
+                break;
+            }
+            if earliest.is_none_or(|earliest| (digit.position, earliest.length) < (earliest.position, digit.length)) {
+                earliest = Some(digit);
+            }
+        }
+        return earliest;
+    }
+
+    /// Longest of the matches ending first in `bytes`, returned as soon as it is found.
+    fn first_ending(&self, bytes: impl Iterator<Item=u8>) -> Option<Digit> {
+        let mut state = 0;
+        for (index, byte) in bytes.enumerate() {
+            state = self.transitions[state][byte as usize] as usize;
+            if let Some(&(length, value)) = self.outputs[state].iter().max_by_key(|(length, _)| *length) {
+                return Some(Digit { position: index + 1 - length, length, value });
+            }
+        }
+        return None;
+    }
+
+    /// Values of the earliest and latest starting matches in a single pass,
+    /// preferring the longest of matches that start at the same position.
+    pub(super) fn first_last(&self, text: &str) -> Option<(u32, u32)> {
+        let mut first: Option<Digit> = None;
+        let mut last: Option<Digit> = None;
+        for digit in self.matches(text) {
+            if first.is_none_or(|first| (digit.position, first.length) < (first.position, digit.length)) {
+                first = Some(digit);
+            }
+            if last.is_none_or(|last| (digit.position, digit.length) > (last.position, last.length)) {
+                last = Some(digit);
+            }
+        }
+        return first.zip(last).map(|(first, last)| (first.value, last.value));
+    }
+}
+
+/// Finds the first digit with a forward scan and the last one with a backward
+/// scan over the reversed patterns, each stopping early. The first match to end
+/// in the reversed line is the one starting last in the original line.
+pub(super) struct Scanner {
+    forward: Automaton,
+    backward: Automaton,
+}
+
+impl Scanner {
+    pub(super) fn new(patterns: &[(&str, u32)]) -> Scanner {
+        let reversed: Vec<(Vec<u8>, u32)> = patterns.iter().map(|(pattern, value)| (pattern.bytes().rev().collect(), *value)).collect();
+        let reversed: Vec<(&[u8], u32)> = reversed.iter().map(|(pattern, value)| (pattern.as_slice(), *value)).collect();
+        return Scanner { forward: Automaton::new(patterns), backward: Automaton::from_bytes(&reversed) };
+    }
+
+    pub(super) fn first(&self, text: &str) -> Option<Digit> {
+        return self.forward.earliest(text.bytes());
+    }
+
+    pub(super) fn last(&self, text: &str) -> Option<Digit> {
+        return self.backward.first_ending(text.bytes().rev())
+            .map(|digit| Digit { position: text.len() - digit.position - digit.length, ..digit });
+    }
+}
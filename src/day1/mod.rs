@@ -1,92 +1,322 @@
-use std::fs;
+use std::{fmt, io};
+use std::fmt::Formatter;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 use std::iter::Iterator;
+use std::time::Instant;
 
-fn parse_input() -> String {
-    let content = fs::read_to_string("src/day1/input.txt").expect("Something went wrong reading the file");
-    return content;
+use automaton::{Automaton, Scanner};
+use crate::verbosity::trace;
+use vocabulary::Vocabulary;
+
+mod automaton;
+mod parallel;
+mod vocabulary;
+
+fn open_input() -> BufReader<File> {
+    let file = File::open("src/day1/input.txt").expect("Something went wrong reading the file");
+    return BufReader::new(file);
 }
 
-const SPELLED_NUMBERS: [(&str, u32); 9] = [
-    ("one", 1),
-    ("two", 2),
-    ("three", 3),
-    ("four", 4),
-    ("five", 5),
-    ("six", 6),
-    ("seven", 7),
-    ("eight", 8),
-    ("nine", 9),
+const NUMERIC_DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
 ];
 
-fn get_spelled(spelled_number: String) -> Option<u32> {
-    if SPELLED_NUMBERS.iter().any(|(spelled, _)| spelled.starts_with(&spelled_number)) {
-        let spelled_number = SPELLED_NUMBERS.iter().find(|(spelled, _)| spelled == &spelled_number);
-        if spelled_number.is_some() {
-            return Option::Some(spelled_number.unwrap().1);
-        }
-    }
-    return Option::None;
-}
-
-fn find_digits(line: String, with_spelled: bool) -> Vec<u32> {
-    let mut digits: Vec<u32> = Vec::new();
-    for (index, char) in line.chars().enumerate() {
-        if with_spelled && char.is_alphabetic() {
-            let mut spelled_number = String::new();
-            let mut found = false;
-            spelled_number.push(char);
-            for next_char in line.chars().skip(index + 1) {
-                let spelled = get_spelled(spelled_number.clone());
-                if spelled.is_some() {
-                    digits.push(spelled.unwrap());
-                    found = true;
-                    break;
-                }
-                spelled_number.push(next_char);
-            }
-            let spelled = get_spelled(spelled_number.clone());
-            if !found && spelled.is_some() {
-                digits.push(spelled.unwrap());
-                break;
-            }
+/// A digit found in a calibration line, with the byte offset and length of its spelling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Digit {
+    position: usize,
+    length: usize,
+    value: u32,
+}
+
+/// Every numeric digit and, with a vocabulary, every spelled digit in `line`.
+/// Spelled digits may overlap, so `eightwo` yields both 8 and 2; of words
+/// starting at the same position only the longest counts.
+fn find_digits(line: &str, spelled: Option<&Vocabulary>) -> Vec<Digit> {
+    let mut digits: Vec<Digit> = Vec::new();
+    for (position, char) in line.char_indices() {
+        if let Some(value) = char.to_digit(10) {
+            digits.push(Digit { position, length: 1, value });
             continue;
         }
-        if char.is_numeric() {
-            digits.push(char.to_digit(10).unwrap());
+        if let Some(vocabulary) = spelled {
+            let longest = vocabulary.words.iter()
+                .filter(|(word, _)| line[position..].starts_with(word.as_str()))
+                .max_by_key(|(word, _)| word.len());
+            if let Some((word, value)) = longest {
+                digits.push(Digit { position, length: word.len(), value: *value });
+            }
         }
     }
     return digits;
 }
 
-fn calculate(content: String, with_spelled: bool) -> i32 {
-    let mut sum: i32 = 0;
+fn patterns(spelled: Option<&Vocabulary>) -> Vec<(&str, u32)> {
+    let mut patterns: Vec<(&str, u32)> = NUMERIC_DIGITS.to_vec();
+    if let Some(vocabulary) = spelled {
+        patterns.extend(vocabulary.words.iter().map(|(word, value)| (word.as_str(), *value)));
+    }
+    return patterns;
+}
+
+fn build_automaton(spelled: Option<&Vocabulary>) -> Automaton {
+    return Automaton::new(&patterns(spelled));
+}
+
+/// What to do with a line that contains no digit at all.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MissingDigits {
+    Skip,
+    Zero,
+    Error,
+}
+
+#[derive(Debug, PartialEq)]
+enum CalibrationError {
+    NoDigits { line: usize },
+    Io(String),
+}
 
-    for (index, line) in content.lines().enumerate() {
-        let digits = find_digits(line.to_string(), with_spelled);
-        let number = format!("{}{}", digits.first().unwrap(), digits.last().unwrap()).parse::<i32>().expect("Invalid input format");
-        println!("{}:{:?},{}", index, digits, number);
+impl From<io::Error> for CalibrationError {
+    fn from(error: io::Error) -> Self {
+        return CalibrationError::Io(error.to_string());
+    }
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CalibrationError::NoDigits { line } => write!(f, "line {line} contains no digits"),
+            CalibrationError::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+/// Sums the calibration values of lines read from `reader` until at least
+/// `limit` bytes are consumed. Returns the number of lines read and their sum;
+/// line numbers in errors count from the first line read.
+fn sum_lines(mut reader: impl BufRead, scanner: &Scanner, missing: MissingDigits, limit: u64, trace_lines: bool) -> Result<(usize, u64), CalibrationError> {
+    // One buffer reused for every line; the scanners only borrow from it.
+    let mut buffer = String::new();
+    let mut consumed: u64 = 0;
+    let mut lines: usize = 0;
+    let mut sum: u64 = 0;
+
+    while consumed < limit {
+        buffer.clear();
+        let read = reader.read_line(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        consumed += read as u64;
+        lines += 1;
+
+        let line = buffer.trim_end_matches(['\n', '\r']);
+        let number = match scanner.first(line).zip(scanner.last(line)) {
+            Some((first, last)) => (first.value * 10 + last.value) as u64,
+            None => match missing {
+                MissingDigits::Skip => continue,
+                MissingDigits::Zero => 0,
+                MissingDigits::Error => return Err(CalibrationError::NoDigits { line: lines }),
+            }
+        };
+        if trace_lines {
+            trace!("{}:{}", lines - 1, number);
+        }
         sum += number
     }
 
-    return sum;
+    return Ok((lines, sum));
+}
+
+fn calculate(reader: impl BufRead, spelled: Option<&Vocabulary>, missing: MissingDigits) -> Result<u64, CalibrationError> {
+    let scanner = Scanner::new(&patterns(spelled));
+    return sum_lines(reader, &scanner, missing, u64::MAX, true).map(|(_, sum)| sum);
+}
+
+/// Calibration document of `lines` lines mixing digits, digit words and filler letters.
+fn synthetic_document(lines: usize, vocabulary: &Vocabulary) -> String {
+    let mut state: u64 = 0x9E3779B97F4A7C15;
+    let mut random = |bound: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        return state as usize % bound;
+    };
+
+    let mut content = String::new();
+    for _ in 0..lines {
+        content.push(char::from_digit(1 + random(9) as u32, 10).unwrap());
+        for _ in 0..random(12) {
+            match random(3) {
+                0 => content.push_str(&vocabulary.words[random(vocabulary.words.len())].0),
+                1 => content.push(char::from_digit(1 + random(9) as u32, 10).unwrap()),
+                _ => content.push((b'a' + random(26) as u8) as char),
+            }
+        }
+        content.push('\n');
+    }
+    return content;
+}
+
+fn benchmark() {
+    let vocabulary = Vocabulary::english();
+    let content = synthetic_document(500_000, &vocabulary);
+    println!("{} lines, {} bytes", content.lines().count(), content.len());
+
+    let started = Instant::now();
+    let mut sum: u64 = 0;
+    for line in content.lines() {
+        let digits = find_digits(line, Some(&vocabulary));
+        sum += (digits.first().unwrap().value * 10 + digits.last().unwrap().value) as u64;
+    }
+    println!("Prefix scan: {sum} in {:?}", started.elapsed());
+
+    let started = Instant::now();
+    let automaton = build_automaton(Some(&vocabulary));
+    let mut sum: u64 = 0;
+    for line in content.lines() {
+        let (first, last) = automaton.first_last(line).unwrap();
+        sum += (first * 10 + last) as u64;
+    }
+    println!("Automaton:   {sum} in {:?}", started.elapsed());
+
+    let started = Instant::now();
+    let scanner = Scanner::new(&patterns(Some(&vocabulary)));
+    let mut sum: u64 = 0;
+    for line in content.lines() {
+        sum += (scanner.first(line).unwrap().value * 10 + scanner.last(line).unwrap().value) as u64;
+    }
+    println!("Both ends:   {sum} in {:?}", started.elapsed());
 }
 
 
 fn puzzle_1() {
-    let content = parse_input();
-    let sum: i32 = calculate(content, false);
+    let sum: u64 = calculate(open_input(), None, MissingDigits::Error).unwrap_or_else(|error| panic!("{error}"));
 
     println!("Sum is {sum}")
 }
 
-fn puzzle_2() {
-    let content = parse_input();
-    let sum: i32 = calculate(content, true);
+fn puzzle_2(vocabulary: &Vocabulary) {
+    let sum: u64 = calculate(open_input(), Some(vocabulary), MissingDigits::Error).unwrap_or_else(|error| panic!("{error}"));
 
     println!("Sum is {sum}")
 }
 
 pub(crate) fn run() {
     // puzzle_1();
-    puzzle_2();
-}
\ No newline at end of file
+    puzzle_2(&Vocabulary::english());
+    // puzzle_2(&Vocabulary::preset("german", true).unwrap());
+    // puzzle_2(&Vocabulary::load("src/day1/vocabulary.txt").unwrap_or_else(|error| panic!("{error}")));
+    // benchmark();
+    // parallel::stress_test("target/calibration.txt", 100_000_000);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_digits_with_positions() {
+        let digits = find_digits("4nineeightseven2", Some(&Vocabulary::english()));
+        let expected = [(0, 1, 4), (1, 4, 9), (5, 5, 8), (10, 5, 7), (15, 1, 2)];
+        assert_eq!(digits, expected.map(|(position, length, value)| Digit { position, length, value }));
+        assert_eq!(find_digits("4nineeightseven2", None), [Digit { position: 0, length: 1, value: 4 }, Digit { position: 15, length: 1, value: 2 }]);
+    }
+
+    #[test]
+    fn finds_every_overlapping_pair_of_words() {
+        let vocabulary = Vocabulary::english();
+        let mut overlapping = 0;
+        for (first, first_value) in vocabulary.words.clone() {
+            for (second, second_value) in vocabulary.words.clone() {
+                for overlap in 1..first.len().min(second.len()) {
+                    if !first.ends_with(&second[..overlap]) { continue; }
+                    let line = format!("{first}{}", &second[overlap..]);
+                    let expected = [
+                        Digit { position: 0, length: first.len(), value: first_value },
+                        Digit { position: first.len() - overlap, length: second.len(), value: second_value },
+                    ];
+                    assert_eq!(find_digits(&line, Some(&vocabulary)), expected, "{line}");
+                    overlapping += 1;
+                }
+            }
+        }
+        // oneight, twone, threeight, fiveight, sevenine, eightwo, eighthree, nineight
+        assert_eq!(overlapping, 8);
+    }
+
+    #[test]
+    fn automaton_agrees_with_prefix_scan() {
+        let vocabulary = Vocabulary::english();
+        let content = synthetic_document(2_000, &vocabulary) + "oneight\ntwone\nsevenine\n0zero\n";
+        for spelled in [None, Some(&vocabulary)] {
+            let automaton = build_automaton(spelled);
+            for line in content.lines() {
+                let mut matches: Vec<Digit> = automaton.matches(line).collect();
+                matches.sort_by_key(|digit| digit.position);
+                assert_eq!(matches, find_digits(line, spelled), "{line}");
+            }
+        }
+    }
+
+    #[test]
+    fn example_calibration_document() {
+        let content = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n";
+        let vocabulary = Vocabulary::english();
+        assert_eq!(calculate(content.as_bytes(), Some(&vocabulary), MissingDigits::Error), Ok(281));
+        assert_eq!(calculate("twone\neightwo\n".as_bytes(), Some(&vocabulary), MissingDigits::Error), Ok(21 + 82));
+    }
+
+    #[test]
+    fn first_and_last_digit_from_both_ends() {
+        let scanner = Scanner::new(&patterns(Some(&Vocabulary::english())));
+        for line in ["two1nine", "xtwone3four", "zoneight234", "7pqrstsixteen", "oneight", "eightwo", "fünf9"] {
+            let digits = find_digits(line, Some(&Vocabulary::english()));
+            assert_eq!(scanner.first(line), digits.first().copied(), "{line}");
+            assert_eq!(scanner.last(line), digits.last().copied(), "{line}");
+        }
+        let roman = Vocabulary::parse("I 1\nIX 9\nXI 2\n").unwrap();
+        let scanner = Scanner::new(&patterns(Some(&roman)));
+        assert_eq!(scanner.first("IXI").map(|digit| digit.value), Some(9));
+        assert_eq!(scanner.last("IXI").map(|digit| digit.value), Some(1));
+        assert_eq!(scanner.last("IXa").map(|digit| digit.value), Some(9));
+    }
+
+    #[test]
+    fn lines_without_digits() {
+        let content = "1abc2\nnothing\n3x\n";
+        assert_eq!(calculate(content.as_bytes(), None, MissingDigits::Skip), Ok(12 + 33));
+        assert_eq!(calculate(content.as_bytes(), None, MissingDigits::Zero), Ok(12 + 33));
+        assert_eq!(calculate(content.as_bytes(), None, MissingDigits::Error), Err(CalibrationError::NoDigits { line: 2 }));
+        assert_eq!(calculate("1\r\n\r\n2".as_bytes(), None, MissingDigits::Error), Err(CalibrationError::NoDigits { line: 2 }));
+    }
+
+    #[test]
+    fn localized_vocabularies() {
+        let german = Vocabulary::preset("german", true).unwrap();
+        assert_eq!(calculate("xfünfzehn3\nnullachtneun\n".as_bytes(), Some(&german), MissingDigits::Error), Ok(53 + 9));
+        let spanish = Vocabulary::preset("spanish", false).unwrap();
+        assert_eq!(calculate("cerodosiete\n".as_bytes(), Some(&spanish), MissingDigits::Error), Ok(27));
+        let french = Vocabulary::preset("french", true).unwrap();
+        assert_eq!(calculate("zéroseptx\n".as_bytes(), Some(&french), MissingDigits::Error), Ok(7));
+        assert_eq!(Vocabulary::preset("klingon", false), None);
+    }
+
+    #[test]
+    fn custom_vocabulary_file_format() {
+        let vocabulary = Vocabulary::parse("# roman numerals\nI 1\nV 5\n\nIX 9\n").unwrap();
+        assert_eq!(calculate("xIXyV\n".as_bytes(), Some(&vocabulary), MissingDigits::Error), Ok(95));
+        assert_eq!(Vocabulary::parse("ten 10\n"), Err(String::from("line 1: \"10\" is not a digit")));
+    }
+}
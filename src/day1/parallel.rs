@@ -0,0 +1,132 @@
+use std::fs;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Instant;
+
+use super::automaton::Scanner;
+use super::vocabulary::Vocabulary;
+use super::{calculate, patterns, sum_lines, synthetic_document, CalibrationError, MissingDigits};
+
+/// Sums the lines starting in `start..end` of the file at `path`. A line
+/// straddling `start` belongs to the previous chunk.
+fn sum_chunk(path: &Path, scanner: &Scanner, missing: MissingDigits, start: u64, end: u64) -> Result<(usize, u64), CalibrationError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut position = start;
+    if start > 0 {
+        reader.seek(SeekFrom::Start(start - 1))?;
+        let mut partial: Vec<u8> = Vec::new();
+        position = start - 1 + reader.read_until(b'\n', &mut partial)? as u64;
+    }
+    if position >= end {
+        return Ok((0, 0));
+    }
+    return sum_lines(reader, scanner, missing, end - position, false);
+}
+
+/// Same result as `calculate` on the whole file, but splits it into `threads`
+/// byte ranges that are scanned concurrently.
+pub(super) fn calculate_parallel(path: &Path, spelled: Option<&Vocabulary>, missing: MissingDigits, threads: usize) -> Result<u64, CalibrationError> {
+    let size = fs::metadata(path)?.len();
+    let threads = threads.max(1) as u64;
+    let chunk = size.div_ceil(threads).max(1);
+    let scanner = Scanner::new(&patterns(spelled));
+
+    let results: Vec<Result<(usize, u64), CalibrationError>> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads).map(|index| {
+            let scanner = &scanner;
+            let start = (index * chunk).min(size);
+            let end = ((index + 1) * chunk).min(size);
+            scope.spawn(move || sum_chunk(path, scanner, missing, start, end))
+        }).collect();
+        workers.into_iter().map(|worker| worker.join().expect("Calibration worker panicked")).collect()
+    });
+
+    // Chunks report line numbers relative to their own first line.
+    let mut lines_before: usize = 0;
+    let mut sum: u64 = 0;
+    for result in results {
+        match result {
+            Ok((lines, chunk_sum)) => {
+                lines_before += lines;
+                sum += chunk_sum;
+            }
+            Err(CalibrationError::NoDigits { line }) => return Err(CalibrationError::NoDigits { line: lines_before + line }),
+            Err(error) => return Err(error),
+        }
+    }
+    return Ok(sum);
+}
+
+fn write_synthetic_file(path: &Path, lines: usize, vocabulary: &Vocabulary) -> Result<(), CalibrationError> {
+    const BATCH: usize = 1_000_000;
+    let mut writer = BufWriter::new(File::create(path)?);
+    let mut written = 0;
+    while written < lines {
+        let batch = BATCH.min(lines - written);
+        writer.write_all(synthetic_document(batch, vocabulary).as_bytes())?;
+        written += batch;
+    }
+    writer.flush()?;
+    return Ok(());
+}
+
+/// Generates a synthetic document of `lines` lines at `path` unless it already
+/// exists, then times the streaming and the parallel scan over it.
+pub(super) fn stress_test(path: &str, lines: usize) {
+    let path = Path::new(path);
+    let vocabulary = Vocabulary::english();
+    if !path.exists() {
+        let start = Instant::now();
+        write_synthetic_file(path, lines, &vocabulary).unwrap_or_else(|error| panic!("{error}"));
+        println!("Generated {} in {:.2?}", path.display(), start.elapsed());
+    }
+    let threads = thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1);
+
+    let start = Instant::now();
+    let file = File::open(path).expect("Something went wrong reading the file");
+    let streamed = calculate(BufReader::new(file), Some(&vocabulary), MissingDigits::Error).unwrap_or_else(|error| panic!("{error}"));
+    println!("Streaming: {streamed} in {:.2?}", start.elapsed());
+
+    let start = Instant::now();
+    let parallel = calculate_parallel(path, Some(&vocabulary), MissingDigits::Error, threads).unwrap_or_else(|error| panic!("{error}"));
+    println!("Parallel ({threads} threads): {parallel} in {:.2?}", start.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn temporary_file(name: &str, content: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("day1-{}-{name}", std::process::id()));
+        fs::write(&path, content).unwrap();
+        return path;
+    }
+
+    #[test]
+    fn parallel_agrees_with_streaming() {
+        let vocabulary = Vocabulary::english();
+        let content = synthetic_document(2_000, &vocabulary);
+        let path = temporary_file("agrees.txt", &content);
+        let expected = calculate(content.as_bytes(), Some(&vocabulary), MissingDigits::Error).unwrap();
+        for threads in [1, 2, 3, 7, 64] {
+            assert_eq!(calculate_parallel(&path, Some(&vocabulary), MissingDigits::Error, threads), Ok(expected));
+        }
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn error_lines_count_across_chunks() {
+        let content = "1\n2\n3\n4\nnone\n5\n6\n";
+        let path = temporary_file("error.txt", content);
+        for threads in [1, 2, 3, 5, 20] {
+            assert_eq!(calculate_parallel(&path, None, MissingDigits::Error, threads), Err(CalibrationError::NoDigits { line: 5 }));
+            assert_eq!(calculate_parallel(&path, None, MissingDigits::Skip, threads), Ok(11 + 22 + 33 + 44 + 55 + 66));
+        }
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,55 @@
+use std::fs;
+
+const ENGLISH: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+const GERMAN: [&str; 10] = ["null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"];
+const FRENCH: [&str; 10] = ["zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf"];
+const SPANISH: [&str; 10] = ["cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve"];
+
+/// Spelled-out digit words recognised next to numeric digits.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Vocabulary {
+    pub(super) words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// The puzzle's vocabulary: English words for one to nine.
+    pub(super) fn english() -> Vocabulary {
+        return Vocabulary::preset("english", false).unwrap();
+    }
+
+    /// Built-in word lists for `english`, `german`, `french` and `spanish`,
+    /// optionally including the word for zero.
+    pub(super) fn preset(language: &str, with_zero: bool) -> Option<Vocabulary> {
+        let words = match language {
+            "english" => ENGLISH,
+            "german" => GERMAN,
+            "french" => FRENCH,
+            "spanish" => SPANISH,
+            _ => return None
+        };
+        let first = if with_zero { 0 } else { 1 };
+        return Some(Vocabulary { words: (first..10).map(|value| (words[value].to_string(), value as u32)).collect() });
+    }
+
+    /// One `<word> <value>` pair per line; empty lines and lines starting with `#` are skipped.
+    pub(super) fn parse(content: &str) -> Result<Vocabulary, String> {
+        let mut words: Vec<(String, u32)> = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let input: Vec<&str> = line.split_whitespace().collect();
+            let [word, value] = <[&str; 2]>::try_from(input).map_err(|_| format!("line {}: expected `<word> <value>`", index + 1))?;
+            let value = value.parse::<u32>().ok().filter(|value| *value < 10)
+                .ok_or(format!("line {}: {value:?} is not a digit", index + 1))?;
+            words.push((word.to_string(), value));
+        }
+        return Ok(Vocabulary { words });
+    }
+
+    pub(super) fn load(path: &str) -> Result<Vocabulary, String> {
+        let content = fs::read_to_string(path).map_err(|error| format!("{path}: {error}"))?;
+        return Vocabulary::parse(&content);
+    }
+}
@@ -0,0 +1,180 @@
+use super::{Color, Game, Palette};
+
+/// Refuse grids larger than this many candidate bags.
+const MAX_CANDIDATES: u64 = 10_000_000;
+
+/// ln C(n, k), 0 for k = 0 and -inf when k > n.
+fn log_binomial(n: u64, k: u64) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k);
+    return (0..k).map(|i| ((n - i) as f64 / (i + 1) as f64).ln()).sum();
+}
+
+/// Log-likelihood of every sample of `game` when each one draws its cubes
+/// uniformly without replacement from a bag holding `counts` cubes of `colors`,
+/// the cubes going back into the bag between samples.
+fn log_likelihood(game: &Game, colors: &[Color], counts: &[u64]) -> f64 {
+    let total: u64 = counts.iter().sum();
+    let mut likelihood = 0.0;
+    for sample in &game.samples {
+        let drawn: u64 = colors.iter().map(|&color| sample.count(color)).sum();
+        let favourable: f64 = colors.iter().zip(counts).map(|(&color, &count)| log_binomial(count, sample.count(color))).sum();
+        if favourable == f64::NEG_INFINITY {
+            return f64::NEG_INFINITY;
+        }
+        likelihood += favourable - log_binomial(total, drawn);
+    }
+    return likelihood;
+}
+
+#[derive(Debug)]
+struct Candidate {
+    counts: Vec<u64>,
+    log_likelihood: f64,
+}
+
+#[derive(Debug)]
+struct Estimate {
+    colors: Vec<Color>,
+    /// Every candidate bag that could have produced the game, most likely first.
+    candidates: Vec<Candidate>,
+    /// Credible interval of the count of each colour, in `colors` order.
+    intervals: Vec<(u64, u64)>,
+}
+
+/// Ranks every bag holding at least the cubes `game` shows and at most
+/// `max_count` of each colour by likelihood. With a flat prior over that grid
+/// the normalised likelihoods are a posterior, whose per-colour marginals give
+/// central intervals at the `confidence` level. The grid has to be bounded:
+/// scaling every count up keeps the likelihood roughly constant, so an open
+/// ended prior would never normalise.
+fn estimate(palette: &Palette, game: &Game, max_count: u64, confidence: f64) -> Result<Estimate, String> {
+    let colors: Vec<Color> = palette.colors().collect();
+    let minimum: Vec<u64> = colors.iter().map(|&color| game.max_cubes(color).unwrap_or(0)).collect();
+    let maximum: Vec<u64> = minimum.iter().map(|&count| count.max(max_count)).collect();
+    let size = minimum.iter().zip(&maximum).try_fold(1u64, |size, (low, high)| size.checked_mul(high - low + 1));
+    if size.is_none_or(|size| size > MAX_CANDIDATES) {
+        return Err(format!("game {} has more than {MAX_CANDIDATES} candidate bags", game.id));
+    }
+
+    let mut candidates: Vec<Candidate> = Vec::new();
+    let mut counts = minimum.clone();
+    'grid: loop {
+        let log_likelihood = log_likelihood(game, &colors, &counts);
+        if log_likelihood.is_finite() {
+            candidates.push(Candidate { counts: counts.clone(), log_likelihood });
+        }
+        for (index, count) in counts.iter_mut().enumerate() {
+            if *count < maximum[index] {
+                *count += 1;
+                continue 'grid;
+            }
+            *count = minimum[index];
+        }
+        break;
+    }
+    if candidates.is_empty() {
+        return Err(format!("no bag can produce game {}", game.id));
+    }
+    candidates.sort_by(|a, b| b.log_likelihood.total_cmp(&a.log_likelihood));
+
+    // Scale by the best candidate so the exponentials stay representable.
+    let best = candidates[0].log_likelihood;
+    let weights: Vec<f64> = candidates.iter().map(|candidate| (candidate.log_likelihood - best).exp()).collect();
+    let total: f64 = weights.iter().sum();
+    let tail = (1.0 - confidence) / 2.0;
+
+    let intervals = (0..colors.len()).map(|index| {
+        let mut marginal = vec![0.0; (maximum[index] - minimum[index] + 1) as usize];
+        for (candidate, weight) in candidates.iter().zip(&weights) {
+            marginal[(candidate.counts[index] - minimum[index]) as usize] += weight / total;
+        }
+        let mut cumulative = 0.0;
+        let mut lower = None;
+        let mut upper = maximum[index];
+        for (offset, probability) in marginal.iter().enumerate() {
+            cumulative += probability;
+            if lower.is_none() && cumulative > tail {
+                lower = Some(minimum[index] + offset as u64);
+            }
+            if cumulative >= 1.0 - tail {
+                upper = minimum[index] + offset as u64;
+                break;
+            }
+        }
+        (lower.unwrap_or(minimum[index]), upper)
+    }).collect();
+
+    return Ok(Estimate { colors, candidates, intervals });
+}
+
+fn print_estimate(palette: &Palette, game: &Game, estimate: &Estimate, top: usize, confidence: f64) {
+    let describe = |counts: &[u64]| -> String {
+        let sets: Vec<String> = estimate.colors.iter().zip(counts).map(|(&color, count)| format!("{count} {}", palette.name(color))).collect();
+        sets.join(", ")
+    };
+
+    println!("Game {}: {} candidate bags", game.id, estimate.candidates.len());
+    for candidate in estimate.candidates.iter().take(top) {
+        println!("  {:>10.4} {}", candidate.log_likelihood, describe(&candidate.counts));
+    }
+    for (&color, (lower, upper)) in estimate.colors.iter().zip(&estimate.intervals) {
+        println!("  {}: {lower}..={upper} ({:.0}%)", palette.name(color), confidence * 100.0);
+    }
+}
+
+/// Prints the `top` most likely bags of every game, with at most `max_count`
+/// cubes per colour, and per-colour intervals at the `confidence` level.
+pub(super) fn run(palette: &Palette, games: &[Game], max_count: u64, confidence: f64, top: usize) {
+    for game in games {
+        match estimate(palette, game, max_count, confidence) {
+            Ok(estimate) => print_estimate(palette, game, &estimate, top, confidence),
+            Err(error) => println!("{error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day2::parse_games;
+
+    #[test]
+    fn hypergeometric_likelihood() {
+        let (palette, games) = parse_games("Game 1: 1 red; 1 red, 1 blue\n").unwrap();
+        let colors = [palette.color("red").unwrap(), palette.color("blue").unwrap()];
+        // 1/2 for the single red cube, then C(1,1) * C(1,1) / C(2,2) = 1 for both.
+        assert!((log_likelihood(&games[0], &colors, &[1, 1]) - 0.5f64.ln()).abs() < 1e-12);
+        // 2/3, then 2 * 1 / C(3,2) = 2/3.
+        assert!((log_likelihood(&games[0], &colors, &[2, 1]) - (4.0f64 / 9.0).ln()).abs() < 1e-12);
+        assert_eq!(log_likelihood(&games[0], &colors, &[1, 0]), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn ranks_bags_and_bounds_counts() {
+        let (palette, games) = parse_games("Game 1: 2 red; 2 red; 1 red, 1 blue\n").unwrap();
+        let estimate = estimate(&palette, &games[0], 6, 0.9).unwrap();
+        let probability = |counts: &[u64]| (log_likelihood(&games[0], &estimate.colors, counts)).exp();
+
+        assert_eq!(estimate.candidates.len(), 5 * 6);
+        assert!(estimate.candidates.windows(2).all(|pair| pair[0].log_likelihood >= pair[1].log_likelihood));
+        // (2/3)^2 * 5/15 for five red cubes beats (5/7)^2 * 6/21 for six.
+        assert_eq!(estimate.candidates[0].counts, vec![5, 1]);
+        assert!(probability(&[5, 1]) > probability(&[6, 1]));
+        assert!(probability(&[5, 1]) > probability(&[2, 1]));
+        assert!(probability(&[5, 1]) > probability(&[6, 6]));
+
+        let (red, blue) = (estimate.intervals[0], estimate.intervals[1]);
+        assert!(2 <= red.0 && red.0 <= red.1 && red.1 <= 6);
+        assert!(1 <= blue.0 && blue.0 <= blue.1 && blue.1 <= 6);
+        assert!(blue.1 < 6, "blue is rarely drawn, so large blue counts are unlikely");
+    }
+
+    #[test]
+    fn refuses_huge_grids() {
+        let (palette, games) = parse_games("Game 1: 1 a, 1 b, 1 c, 1 d, 1 e, 1 f\n").unwrap();
+        assert!(estimate(&palette, &games[0], 100, 0.9).is_err());
+    }
+}
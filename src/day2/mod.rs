@@ -1,46 +1,61 @@
-use std::fs;
+use std::collections::HashMap;
+use std::{fmt, fs};
+use std::fmt::Formatter;
+use std::mem;
 use std::str::FromStr;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-enum Color {
-    RED,
-    GREEN,
-    BLUE,
-}
+mod inference;
+mod report;
+
+/// Cube colour, interned by a `Palette`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Color(usize);
 
-impl FromStr for Color {
-    type Err = ();
+/// Every colour name seen so far, in order of first appearance.
+#[derive(Debug, Default)]
+struct Palette {
+    names: Vec<String>,
+    ids: HashMap<String, Color>,
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "red" => Ok(Color::RED),
-            "green" => Ok(Color::GREEN),
-            "blue" => Ok(Color::BLUE),
-            _ => Err(())
+impl Palette {
+    fn intern(&mut self, name: &str) -> Color {
+        if let Some(&color) = self.ids.get(name) {
+            return color;
         }
+        let color = Color(self.names.len());
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), color);
+        return color;
     }
-}
 
-#[derive(Debug)]
-struct Cube {
-    color: Color,
-}
+    /// Colour with this name, `None` if the input never uses it.
+    fn color(&self, name: &str) -> Option<Color> {
+        return self.ids.get(name).copied();
+    }
 
+    fn colors(&self) -> impl Iterator<Item=Color> {
+        return (0..self.names.len()).map(Color);
+    }
 
-#[derive(Debug)]
+    fn name(&self, color: Color) -> &str {
+        return &self.names[color.0];
+    }
+}
+
+/// Cubes revealed at once, per colour.
+#[derive(Debug, Default)]
 struct Sample {
-    cubes: Vec<Cube>,
+    counts: HashMap<Color, u64>,
 }
 
 impl Sample {
-    fn can_fit(&self, count: &i32, color: &Color) -> bool {
-        let mut remaining = count.clone();
-        for cube in &self.cubes {
-            if cube.color == *color {
-                remaining -= 1
-            }
-        }
-        return remaining >= 0;
+    fn count(&self, color: Color) -> u64 {
+        return self.counts.get(&color).copied().unwrap_or(0);
+    }
+
+    fn can_fit(&self, count: u64, color: Color) -> bool {
+        return self.count(color) <= count;
     }
 }
 
@@ -52,80 +67,296 @@ struct Game {
 }
 
 impl Game {
-    fn max_cubes<'a>(&self, color: Color) -> Option<usize> {
-        return self.samples.iter().map(|sample| sample.cubes.iter().filter(|cube| cube.color == color).count()).max();
+    fn max_cubes(&self, color: Color) -> Option<u64> {
+        return self.samples.iter().map(|sample| sample.count(color)).max();
+    }
+
+    /// Most cubes of `color` drawn at once, with the index of the first sample drawing them.
+    fn max_sample(&self, color: Color) -> Option<(u64, usize)> {
+        return self.samples.iter().enumerate()
+            .map(|(index, sample)| (sample.count(color), index))
+            .reduce(|best, current| if current.0 > best.0 { current } else { best });
     }
 }
 
 struct Bag {
-    sets: Vec<(i32, Color)>,
+    sets: Vec<(u64, Color)>,
 }
 
-fn parse_input() -> Vec<Game> {
-    let content = fs::read_to_string("src/day2/input.txt").expect("Something went wrong reading the file");
-    let mut games: Vec<Game> = Vec::new();
+impl Bag {
+    /// Bag holding `limits` cubes per colour name and none of any other colour in `palette`.
+    /// Limits for colours missing from `palette` cannot affect any game and are dropped.
+    fn new(palette: &Palette, limits: &[(u64, &str)]) -> Bag {
+        let limits: Vec<(u64, Color)> = limits.iter().filter_map(|&(count, name)| Some((count, palette.color(name)?))).collect();
+        let sets = palette.colors().map(|color| {
+            let count = limits.iter().find(|(_, limit)| *limit == color).map_or(0, |&(count, _)| count);
+            (count, color)
+        }).collect();
+        return Bag { sets };
+    }
 
-    for line in content.lines() {
-        let mut samples: Vec<Sample> = Vec::new();
+    /// Smallest bag that could have produced every sample of `game`.
+    fn minimal(palette: &Palette, game: &Game) -> Bag {
+        return Bag { sets: palette.colors().map(|color| (game.max_cubes(color).unwrap_or(0), color)).collect() };
+    }
 
-        let input: Vec<&str> = line.split(":").collect();
-        let [game_input, samples_input] = <[&str; 2]>::try_from(input).ok().unwrap();
-        let samples_set: Vec<&str> = samples_input.split(";").collect();
+    /// Product of the cube counts, `None` if it does not fit in a u128.
+    fn power(&self) -> Option<u128> {
+        return self.sets.iter().try_fold(1u128, |power, &(count, _)| power.checked_mul(count as u128));
+    }
+}
 
-        for sample in samples_set {
-            let mut cubes: Vec<Cube> = Vec::new();
-            let cubes_set: Vec<&str> = sample.split(",").collect();
+#[derive(Debug, PartialEq)]
+enum ParseError {
+    UnexpectedToken { line: usize, column: usize, expected: &'static str, found: String },
+    UnexpectedEnd { line: usize, column: usize, expected: &'static str },
+    InvalidNumber { line: usize, column: usize, number: String },
+    CountOverflow { line: usize, column: usize, color: String },
+}
 
-            for cube_set in cubes_set {
-                let cube_set: Vec<&str> = cube_set[1..].split(" ").collect();
-                let [count, color_str] = <[&str; 2]>::try_from(cube_set).ok().unwrap();
-                let color = Color::from_str(color_str).unwrap();
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedToken { line, column, expected, found } => write!(f, "line {line}, column {column}: expected {expected}, found {found:?}"),
+            ParseError::UnexpectedEnd { line, column, expected } => write!(f, "line {line}, column {column}: expected {expected}, found end of line"),
+            ParseError::InvalidNumber { line, column, number } => write!(f, "line {line}, column {column}: number {number} is too large"),
+            ParseError::CountOverflow { line, column, color } => write!(f, "line {line}, column {column}: too many {color} cubes in one sample"),
+        }
+    }
+}
 
-                for _ in 0..count.parse::<i32>().unwrap() {
-                    cubes.push(Cube { color })
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Number(&'a str),
+    Word(&'a str),
+    Colon,
+    Comma,
+    Semicolon,
+}
+
+impl Token<'_> {
+    fn text(&self) -> &str {
+        return match self {
+            Token::Number(text) | Token::Word(text) => text,
+            Token::Colon => ":",
+            Token::Comma => ",",
+            Token::Semicolon => ";",
+        };
+    }
+}
+
+/// Splits a line into tokens with their 1-based columns. Any amount of
+/// whitespace may separate tokens; a word runs until whitespace or punctuation.
+fn tokenize(line: &str) -> Vec<(usize, Token<'_>)> {
+    let mut tokens: Vec<(usize, Token<'_>)> = Vec::new();
+    let mut chars = line.char_indices().enumerate().peekable();
+
+    while let Some((column, (start, c))) = chars.next() {
+        let column = column + 1;
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            ':' => Token::Colon,
+            ',' => Token::Comma,
+            ';' => Token::Semicolon,
+            c => {
+                let is_number = c.is_ascii_digit();
+                let mut end = start + c.len_utf8();
+                while let Some(&(_, (index, next))) = chars.peek() {
+                    let continues = if is_number { next.is_ascii_digit() } else { !next.is_whitespace() && !":,;".contains(next) };
+                    if !continues { break; }
+                    end = index + next.len_utf8();
+                    chars.next();
                 }
+                if is_number { Token::Number(&line[start..end]) } else { Token::Word(&line[start..end]) }
             }
+        };
+        tokens.push((column, token));
+    }
+    return tokens;
+}
+
+/// Recursive descent over the tokens of one `Game <id>: <count> <colour>, ...; ...` line.
+struct GameParser<'a> {
+    tokens: Vec<(usize, Token<'a>)>,
+    position: usize,
+    line: usize,
+    /// Column just past the last character, reported when the line ends early.
+    end: usize,
+}
 
-            samples.push(Sample { cubes })
+impl<'a> GameParser<'a> {
+    fn next(&mut self, expected: &'static str) -> Result<(usize, Token<'a>), ParseError> {
+        let Some((column, token)) = self.tokens.get(self.position) else {
+            return Err(ParseError::UnexpectedEnd { line: self.line, column: self.end, expected });
+        };
+        self.position += 1;
+        return Ok((*column, *token));
+    }
+
+    fn unexpected(&self, column: usize, expected: &'static str, token: &Token) -> ParseError {
+        return ParseError::UnexpectedToken { line: self.line, column, expected, found: token.text().to_string() };
+    }
+
+    fn expect(&mut self, expected: Token<'static>, description: &'static str) -> Result<(), ParseError> {
+        let (column, token) = self.next(description)?;
+        if token != expected {
+            return Err(self.unexpected(column, description, &token));
+        }
+        return Ok(());
+    }
+
+    fn number<T: FromStr>(&mut self, expected: &'static str) -> Result<T, ParseError> {
+        let (column, token) = self.next(expected)?;
+        let Token::Number(number) = token else {
+            return Err(self.unexpected(column, expected, &token));
+        };
+        return number.parse::<T>().map_err(|_| ParseError::InvalidNumber { line: self.line, column, number: number.to_string() });
+    }
+
+    fn word(&mut self, expected: &'static str) -> Result<&'a str, ParseError> {
+        let (column, token) = self.next(expected)?;
+        let Token::Word(word) = token else {
+            return Err(self.unexpected(column, expected, &token));
+        };
+        return Ok(word);
+    }
+
+    fn game(&mut self, palette: &mut Palette) -> Result<Game, ParseError> {
+        self.expect(Token::Word("Game"), "`Game`")?;
+        let id = self.number::<i32>("a game id")?;
+        self.expect(Token::Colon, "`:`")?;
+
+        let mut samples: Vec<Sample> = Vec::new();
+        let mut cubes = Sample::default();
+        loop {
+            let column = self.tokens.get(self.position).map_or(self.end, |(column, _)| *column);
+            let count = self.number::<u64>("a cube count")?;
+            let name = self.word("a colour")?;
+            let total = cubes.counts.entry(palette.intern(name)).or_insert(0);
+            *total = total.checked_add(count)
+                .ok_or(ParseError::CountOverflow { line: self.line, column, color: name.to_string() })?;
+
+            match self.tokens.get(self.position) {
+                None => break,
+                Some((_, Token::Comma)) => self.position += 1,
+                Some((_, Token::Semicolon)) => {
+                    self.position += 1;
+                    samples.push(mem::take(&mut cubes));
+                }
+                Some((column, token)) => return Err(self.unexpected(*column, "`,`, `;` or end of line", token)),
+            }
         }
+        samples.push(cubes);
+        return Ok(Game { id, samples });
+    }
+}
+
+fn parse_games(content: &str) -> Result<(Palette, Vec<Game>), ParseError> {
+    let mut palette = Palette::default();
+    let mut games: Vec<Game> = Vec::new();
 
-        let game_input: Vec<&str> = game_input.split(" ").collect();
-        let [_, game_id] = <[&str; 2]>::try_from(game_input).ok().unwrap();
-        let game_id = game_id.parse::<i32>().unwrap();
-        games.push(Game { id: game_id, samples })
+    for (index, line) in content.lines().enumerate() {
+        let tokens = tokenize(line);
+        if tokens.is_empty() {
+            continue;
+        }
+        let mut parser = GameParser { tokens, position: 0, line: index + 1, end: line.chars().count() + 1 };
+        games.push(parser.game(&mut palette)?);
     }
-    return games;
+    return Ok((palette, games));
 }
 
+fn parse_input() -> (Palette, Vec<Game>) {
+    let content = fs::read_to_string("src/day2/input.txt").expect("Something went wrong reading the file");
+    return parse_games(&content).unwrap_or_else(|error| panic!("{error}"));
+}
 
-fn puzzle_1() {
-    let games = parse_input();
-    let bag = Bag { sets: vec![(12, Color::RED), (13, Color::GREEN), (14, Color::BLUE)] };
-    let mut sum: i32 = 0;
+/// First sample of a game that does not fit in the bag.
+#[derive(Debug, PartialEq)]
+struct Failure {
+    game: i32,
+    /// 1-based index of the sample within the game.
+    sample: usize,
+    /// Colours drawn beyond the bag: (colour, drawn, held).
+    overdrawn: Vec<(Color, u64, u64)>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Feasibility {
+    possible: Vec<i32>,
+    failures: Vec<Failure>,
+}
+
+impl Feasibility {
+    /// Summed as i64, which no realistic number of i32 ids can overflow.
+    fn id_sum(&self) -> i64 {
+        return self.possible.iter().map(|&id| id as i64).sum();
+    }
+}
+
+fn check_games(bag: &Bag, games: &[Game]) -> Feasibility {
+    let mut feasibility = Feasibility { possible: Vec::new(), failures: Vec::new() };
 
     for game in games {
-        let is_possible = game.samples.iter().all(|sample| bag.sets.iter().all(|(count, color)| sample.can_fit(count, color)));
-        if is_possible { sum += game.id }
+        let failure = game.samples.iter().enumerate().find_map(|(index, sample)| {
+            let overdrawn: Vec<(Color, u64, u64)> = bag.sets.iter()
+                .filter(|&&(count, color)| !sample.can_fit(count, color))
+                .map(|&(count, color)| (color, sample.count(color), count))
+                .collect();
+            if overdrawn.is_empty() { None } else { Some(Failure { game: game.id, sample: index + 1, overdrawn }) }
+        });
+        match failure {
+            Some(failure) => feasibility.failures.push(failure),
+            None => feasibility.possible.push(game.id),
+        }
     }
+    return feasibility;
+}
 
-    println!("{:?}", sum)
+fn possible_games_sum(palette: &Palette, games: &[Game], limits: &[(u64, &str)]) -> i64 {
+    let bag = Bag::new(palette, limits);
+    return check_games(&bag, games).id_sum();
 }
 
-fn puzzle_2() {
-    let games = parse_input();
-    let mut sum: i32 = 0;
+fn print_feasibility(palette: &Palette, feasibility: &Feasibility) {
+    let possible: Vec<String> = feasibility.possible.iter().map(|id| id.to_string()).collect();
+    println!("Possible games: {}", possible.join(", "));
+    for failure in &feasibility.failures {
+        let overdrawn: Vec<String> = failure.overdrawn.iter()
+            .map(|&(color, drawn, held)| format!("{drawn} {} (bag holds {held})", palette.name(color)))
+            .collect();
+        println!("Game {}: sample {} draws {}", failure.game, failure.sample, overdrawn.join(", "));
+    }
+    println!("Sum is {}", feasibility.id_sum());
+}
 
-    for game in games {
-        let bag = Bag {
-            sets: vec![
-                (game.max_cubes(Color::RED).unwrap_or(0) as i32, Color::RED),
-                (game.max_cubes(Color::GREEN).unwrap_or(0) as i32, Color::GREEN),
-                (game.max_cubes(Color::BLUE).unwrap_or(0) as i32, Color::BLUE),
-            ]
-        };
-        let power: i32 = bag.sets.iter().map(|(count, _)| count).product();
-        sum += power
+/// Checks the puzzle input against a bag given as (colour, count) pairs.
+pub(crate) fn check_bag(limits: &[(String, u64)]) {
+    let (palette, games) = parse_input();
+    let limits: Vec<(u64, &str)> = limits.iter().map(|(color, count)| (*count, color.as_str())).collect();
+    for (_, name) in limits.iter().filter(|(_, name)| palette.color(name).is_none()) {
+        println!("No game draws {name} cubes, ignoring its limit");
     }
+    let bag = Bag::new(&palette, &limits);
+    print_feasibility(&palette, &check_games(&bag, &games));
+}
+
+/// Sum of the powers of every game's minimal bag, `None` if it does not fit in a u128.
+fn power_sum(palette: &Palette, games: &[Game]) -> Option<u128> {
+    return games.iter().try_fold(0u128, |sum, game| sum.checked_add(Bag::minimal(palette, game).power()?));
+}
+
+
+fn puzzle_1() {
+    let (palette, games) = parse_input();
+    let sum = possible_games_sum(&palette, &games, &[(12, "red"), (13, "green"), (14, "blue")]);
+
+    println!("{:?}", sum)
+}
+
+fn puzzle_2() {
+    let (palette, games) = parse_input();
+    let sum = power_sum(&palette, &games).unwrap_or_else(|| panic!("Sum of powers does not fit in u128"));
 
     println!("{:?}", sum)
 }
@@ -133,4 +364,114 @@ fn puzzle_2() {
 pub(crate) fn run() {
     // puzzle_1()
     puzzle_2()
-}
\ No newline at end of file
+    // let (palette, games) = parse_input();
+    // report::export(&palette, &games, "day2.csv");
+    // inference::run(&palette, &games, 20, 0.95, 3);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_colors_are_interned() {
+        let (palette, games) = parse_games("Game 1: 2 yellow, 1 red; 3 yellow\nGame 2: 1 red, 1 blue\n").unwrap();
+        assert_eq!(palette.colors().map(|color| palette.name(color).to_string()).collect::<Vec<String>>(), vec!["yellow", "red", "blue"]);
+        assert_eq!(games[0].max_cubes(palette.color("yellow").unwrap()), Some(3));
+
+        // Colours missing from the bag hold no cubes, colours missing from a game have a minimum of zero.
+        assert_eq!(possible_games_sum(&palette, &games, &[(1, "red"), (1, "blue")]), 2);
+        assert_eq!(possible_games_sum(&palette, &games, &[(1, "red"), (1, "blue"), (3, "yellow")]), 1 + 2);
+        assert_eq!(power_sum(&palette, &games), Some(0));
+        assert_eq!(Bag::minimal(&palette, &games[0]).power(), Some(0));
+    }
+
+    #[test]
+    fn example_games() {
+        let content = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+            Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n\
+            Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\n\
+            Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\n\
+            Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green\n";
+        let (palette, games) = parse_games(content).unwrap();
+        assert_eq!(possible_games_sum(&palette, &games, &[(12, "red"), (13, "green"), (14, "blue")]), 8);
+        assert_eq!(power_sum(&palette, &games), Some(2286));
+    }
+
+    #[test]
+    fn bag_queries_leave_the_palette_alone() {
+        let (palette, games) = parse_games("Game 1: 2 red, 3 green\n").unwrap();
+        assert_eq!(power_sum(&palette, &games), Some(6));
+        assert_eq!(possible_games_sum(&palette, &games, &[(12, "red"), (13, "green"), (14, "blue")]), 1);
+        assert_eq!(palette.color("blue"), None);
+        assert_eq!(power_sum(&palette, &games), Some(6));
+    }
+
+    #[test]
+    fn id_sum_beyond_i32() {
+        let (palette, games) = parse_games("Game 2147483647: 1 red\nGame 2147483646: 1 red\n").unwrap();
+        assert_eq!(possible_games_sum(&palette, &games, &[(1, "red")]), 2 * i32::MAX as i64 - 1);
+    }
+
+    #[test]
+    fn reports_failing_samples() {
+        let (palette, games) = parse_games("Game 1: 3 blue, 4 red; 1 red, 2 green\nGame 3: 8 green, 20 red; 5 blue, 15 red, 14 green\nGame 4: 3 red; 15 blue, 14 red\n").unwrap();
+        let bag = Bag::new(&palette, &[(12, "red"), (13, "green"), (14, "blue")]);
+        let (red, blue) = (palette.color("red").unwrap(), palette.color("blue").unwrap());
+        assert_eq!(check_games(&bag, &games), Feasibility {
+            possible: vec![1],
+            failures: vec![
+                Failure { game: 3, sample: 1, overdrawn: vec![(red, 20, 12)] },
+                Failure { game: 4, sample: 2, overdrawn: vec![(blue, 15, 14), (red, 14, 12)] },
+            ],
+        });
+    }
+
+    #[test]
+    fn large_counts() {
+        let (palette, games) = parse_games("Game 7: 9000000000 red, 1 red, 3 blue; 2 blue\n").unwrap();
+        let red = palette.color("red").unwrap();
+        assert_eq!(games[0].samples[0].count(red), 9_000_000_001);
+        assert_eq!(games[0].max_cubes(red), Some(9_000_000_001));
+        assert_eq!(possible_games_sum(&palette, &games, &[(9_000_000_000, "red"), (3, "blue")]), 0);
+        assert_eq!(possible_games_sum(&palette, &games, &[(9_000_000_001, "red"), (3, "blue")]), 7);
+        assert_eq!(power_sum(&palette, &games), Some(27_000_000_003));
+    }
+
+    #[test]
+    fn counts_beyond_u64_and_powers_beyond_u128() {
+        let (palette, games) = parse_games("Game 1: 18446744073709551615 red, 18446744073709551615 green, 3 blue\n").unwrap();
+        assert_eq!(Bag::minimal(&palette, &games[0]).power(), None);
+        assert_eq!(power_sum(&palette, &games), None);
+
+        let (palette, games) = parse_games("Game 1: 18446744073709551615 red, 3 green\nGame 2: 18446744073709551615 red, 18446744073709551615 green\n").unwrap();
+        assert_eq!(Bag::minimal(&palette, &games[1]).power(), Some(u64::MAX as u128 * u64::MAX as u128));
+        assert_eq!(power_sum(&palette, &games), None);
+
+        assert_eq!(parse_games("Game 1: 2 blue;\t18446744073709551615 red, 1 red\n").err(), Some(ParseError::CountOverflow { line: 1, column: 43, color: String::from("red") }));
+    }
+
+    #[test]
+    fn tolerates_whitespace() {
+        let (palette, games) = parse_games("  Game\t12 :3 blue ,4  red;\t1 red,2 green ;2\tgreen  \n\n   \nGame 13:1 blue\r\n").unwrap();
+        let (blue, red, green) = (palette.color("blue").unwrap(), palette.color("red").unwrap(), palette.color("green").unwrap());
+        assert_eq!(games.len(), 2);
+        assert_eq!((games[0].id, games[0].samples.len()), (12, 3));
+        assert_eq!((games[0].samples[0].count(blue), games[0].samples[0].count(red)), (3, 4));
+        assert_eq!((games[0].samples[1].count(red), games[0].samples[1].count(green)), (1, 2));
+        assert_eq!(games[0].samples[2].count(green), 2);
+        assert_eq!((games[1].id, games[1].samples[0].count(blue)), (13, 1));
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        let error = |content: &str| parse_games(content).err().map(|error| error.to_string());
+        assert_eq!(parse_games("Game 1: 3 blue;\n").err(), Some(ParseError::UnexpectedEnd { line: 1, column: 16, expected: "a cube count" }));
+        assert_eq!(parse_games("Game 1: 3 blue\nGame 2: 3 blue 4 red\n").err(), Some(ParseError::UnexpectedToken { line: 2, column: 16, expected: "`,`, `;` or end of line", found: String::from("4") }));
+        assert_eq!(error("Gmae 1: 3 blue"), Some(String::from("line 1, column 1: expected `Game`, found \"Gmae\"")));
+        assert_eq!(error("Game 1: blue 3"), Some(String::from("line 1, column 9: expected a cube count, found \"blue\"")));
+        assert_eq!(error("Game 1 3 blue"), Some(String::from("line 1, column 8: expected `:`, found \"3\"")));
+        assert_eq!(error("Game 1: 3 blue,, 4 red"), Some(String::from("line 1, column 16: expected a cube count, found \",\"")));
+        assert_eq!(error("Game 9999999999: 3 blue"), Some(String::from("line 1, column 6: number 9999999999 is too large")));
+    }
+}
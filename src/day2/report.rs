@@ -0,0 +1,117 @@
+use std::fmt::Write;
+use std::fs;
+
+use super::{Bag, Color, Game, Palette};
+
+/// Smallest bag for one game, with the sample that needed each colour's count.
+#[derive(Debug, PartialEq)]
+struct MinimalBag {
+    game: i32,
+    /// (colour, count, 1-based sample drawing that many), for every colour of the palette.
+    /// The sample is `None` for colours the game never draws.
+    counts: Vec<(Color, u64, Option<usize>)>,
+    /// `None` if the power does not fit in a u128.
+    power: Option<u128>,
+}
+
+fn minimal_bags(palette: &Palette, games: &[Game]) -> Vec<MinimalBag> {
+    return games.iter().map(|game| {
+        let counts = palette.colors().map(|color| match game.max_sample(color) {
+            Some((count, sample)) if count > 0 => (color, count, Some(sample + 1)),
+            _ => (color, 0, None),
+        }).collect();
+        MinimalBag { game: game.id, counts, power: Bag::minimal(palette, game).power() }
+    }).collect();
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+    return value.to_string();
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    return escaped;
+}
+
+fn to_csv(palette: &Palette, bags: &[MinimalBag]) -> String {
+    let mut csv = String::from("game,power");
+    for color in palette.colors() {
+        let name = palette.name(color);
+        write!(csv, ",{},{}", csv_field(name), csv_field(&format!("{name}_sample"))).unwrap();
+    }
+    csv.push('\n');
+
+    for bag in bags {
+        write!(csv, "{},{}", bag.game, bag.power.map_or(String::new(), |power| power.to_string())).unwrap();
+        for &(_, count, sample) in &bag.counts {
+            write!(csv, ",{count},{}", sample.map_or(String::new(), |sample| sample.to_string())).unwrap();
+        }
+        csv.push('\n');
+    }
+    return csv;
+}
+
+fn to_json(palette: &Palette, bags: &[MinimalBag]) -> String {
+    let games: Vec<String> = bags.iter().map(|bag| {
+        let counts: Vec<String> = bag.counts.iter().map(|&(color, count, sample)| {
+            let sample = sample.map_or(String::from("null"), |sample| sample.to_string());
+            format!("{}: {{\"count\": {count}, \"sample\": {sample}}}", json_string(palette.name(color)))
+        }).collect();
+        let power = bag.power.map_or(String::from("null"), |power| power.to_string());
+        format!("  {{\"game\": {}, \"power\": {power}, \"bag\": {{{}}}}}", bag.game, counts.join(", "))
+    }).collect();
+    return format!("[\n{}\n]\n", games.join(",\n"));
+}
+
+/// Writes the minimal bag of every game to `path`, as CSV or JSON depending on its extension.
+pub(super) fn export(palette: &Palette, games: &[Game], path: &str) {
+    let bags = minimal_bags(palette, games);
+    let content = if path.ends_with(".csv") {
+        to_csv(palette, &bags)
+    } else if path.ends_with(".json") {
+        to_json(palette, &bags)
+    } else {
+        panic!("Unknown report format for {path}, expected .csv or .json")
+    };
+    fs::write(path, content).expect("Something went wrong writing the file");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day2::parse_games;
+
+    #[test]
+    fn minimal_bags_as_csv_and_json() {
+        let (palette, games) = parse_games("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue; 2 \"sky\"\n").unwrap();
+        let bags = minimal_bags(&palette, &games);
+        assert_eq!(bags[0], MinimalBag { game: 1, counts: vec![(Color(0), 6, Some(2)), (Color(1), 4, Some(1)), (Color(2), 2, Some(2)), (Color(3), 0, None)], power: Some(0) });
+
+        assert_eq!(to_csv(&palette, &bags), "game,power,blue,blue_sample,red,red_sample,green,green_sample,\"\"\"sky\"\"\",\"\"\"sky\"\"_sample\"\n\
+            1,0,6,2,4,1,2,2,0,\n\
+            2,0,1,1,0,,0,,2,2\n");
+        assert_eq!(to_json(&palette, &bags[1..]), "[\n  {\"game\": 2, \"power\": 0, \"bag\": {\"blue\": {\"count\": 1, \"sample\": 1}, \
+            \"red\": {\"count\": 0, \"sample\": null}, \"green\": {\"count\": 0, \"sample\": null}, \"\\\"sky\\\"\": {\"count\": 2, \"sample\": 2}}}\n]\n");
+    }
+
+    #[test]
+    fn overflowing_powers_are_left_empty() {
+        let (palette, games) = parse_games("Game 1: 18446744073709551615 red, 18446744073709551615 green, 3 blue\n").unwrap();
+        let bags = minimal_bags(&palette, &games);
+        assert_eq!(bags[0].power, None);
+        assert_eq!(to_csv(&palette, &bags), "game,power,red,red_sample,green,green_sample,blue,blue_sample\n1,,18446744073709551615,1,18446744073709551615,1,3,1\n");
+        assert!(to_json(&palette, &bags).contains("\"power\": null"));
+    }
+}
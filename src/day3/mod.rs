@@ -1,7 +1,10 @@
-use std::fs;
+use std::{fmt, fs};
+use std::fmt::Formatter;
 use std::ops::Range;
 use std::str::FromStr;
 
+use crate::grid::{Grid, Position, ADJACENT};
+
 #[derive(Debug, Clone, PartialEq)]
 enum Part {
     SYMBOL(String),
@@ -25,6 +28,16 @@ impl FromStr for Part {
     }
 }
 
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::SYMBOL(symbol) => write!(f, "{symbol}"),
+            Part::NUMBER(digit) => write!(f, "{digit}"),
+            Part::PERIOD => write!(f, "."),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct Number {
     value: i32,
@@ -42,23 +55,19 @@ impl Number {
         return self.position.0 == pos.0 && (self.position.1.end == pos.1 || self.position.1.contains(&pos.1));
     }
 
-    fn surroundings(&self) -> Vec<(usize, usize)> {
-        let mut positions: Vec<(usize, usize)> = Vec::new();
-        let row = self.position.0;
-        let start: usize;
-        if self.position.1.start == 0 {
-            start = self.position.1.start;
-        } else {
-            start = self.position.1.start - 1;
-        }
-        let end = self.position.1.end + 1;
+    /// Cells around the number, diagonals included, that lie within `grid`.
+    fn surroundings<T>(&self, grid: &Grid<T>) -> Vec<Position> {
+        let (row, columns) = (self.position.0, &self.position.1);
+        let length = (columns.end - columns.start) as isize;
+        let mut positions: Vec<Position> = Vec::new();
 
-        for column in start..end + 1 {
-            if row != 0 {
-                positions.push((row - 1, column));
+        for row_offset in -1..=1 {
+            for column_offset in -1..=length + 1 {
+                let inside = row_offset == 0 && (0..=length).contains(&column_offset);
+                if let (false, Some(position)) = (inside, grid.offset((row, columns.start), (row_offset, column_offset))) {
+                    positions.push(position);
+                }
             }
-            positions.push((row, column));
-            positions.push((row + 1, column))
         }
 
         return positions;
@@ -67,18 +76,10 @@ impl Number {
 
 #[derive(Debug)]
 struct EngineSchematic {
-    rows: Vec<Vec<Part>>,
+    grid: Grid<Part>,
     numbers: Vec<Number>,
 }
 
-impl EngineSchematic {
-    fn fits(&self, position: (&usize, &usize)) -> bool {
-        let height = self.rows.len();
-        let width = self.rows[0].len();
-        return &0 <= position.0 && position.0 < &height && &0 <= position.1 && position.1 < &width;
-    }
-}
-
 fn parse_input() -> EngineSchematic {
     let content = fs::read_to_string("src/day3/input.txt").expect("Something went wrong reading the file");
     let mut rows: Vec<Vec<Part>> = Vec::new();
@@ -111,23 +112,21 @@ fn parse_input() -> EngineSchematic {
         }
         rows.push(row)
     }
-    return EngineSchematic { rows, numbers };
+    let grid = Grid::from_rows(rows).unwrap_or_else(|error| panic!("{error}"));
+    return EngineSchematic { grid, numbers };
 }
 
 fn puzzle_1() {
     let engine_schematic = parse_input();
     let mut sum = 0;
     for number in &engine_schematic.numbers {
-        for (row, column) in number.surroundings() {
-            if engine_schematic.fits((&row, &column)) {
-                let part = engine_schematic.rows[row][column].clone();
-                match part {
-                    Part::SYMBOL(_) => {
-                        sum += number.value;
-                        break;
-                    }
-                    _ => {}
+        for position in number.surroundings(&engine_schematic.grid) {
+            match engine_schematic.grid[position] {
+                Part::SYMBOL(_) => {
+                    sum += number.value;
+                    break;
                 }
+                _ => {}
             }
         }
     }
@@ -137,36 +136,26 @@ fn puzzle_1() {
 fn puzzle_2() {
     let engine_schematic = parse_input();
     let mut ratio = 0;
-    for (row_index, row) in engine_schematic.rows.iter().enumerate() {
-        for (col_index, part) in row.iter().enumerate() {
-            match part {
-                Part::SYMBOL(sym) => {
-                    if sym != "*" { continue; }
-                    let mut matched: Vec<Number> = Vec::new();
-                    let surroundings = vec![
-                        (row_index - 1, col_index - 1),
-                        (row_index - 1, col_index),
-                        (row_index - 1, col_index + 1),
-                        (row_index, col_index - 1),
-                        (row_index, col_index + 1),
-                        (row_index + 1, col_index - 1),
-                        (row_index + 1, col_index),
-                        (row_index + 1, col_index + 1),
-                    ];
-                    for number in &engine_schematic.numbers {
-                        for sur in &surroundings {
-                            if number.has_position(*sur) {
-                                matched.push(number.clone());
-                                break;
-                            }
+    let grid = &engine_schematic.grid;
+    for position in grid.positions() {
+        match &grid[position] {
+            Part::SYMBOL(sym) => {
+                if sym != "*" { continue; }
+                let mut matched: Vec<Number> = Vec::new();
+                let surroundings: Vec<Position> = grid.neighbours(position, &ADJACENT).collect();
+                for number in &engine_schematic.numbers {
+                    for sur in &surroundings {
+                        if number.has_position(*sur) {
+                            matched.push(number.clone());
+                            break;
                         }
                     }
-                    if matched.len() == 2 {
-                        ratio += matched[0].value * matched[1].value;
-                    }
                 }
-                _ => {}
+                if matched.len() == 2 {
+                    ratio += matched[0].value * matched[1].value;
+                }
             }
+            _ => {}
         }
     }
     println!("{ratio}")
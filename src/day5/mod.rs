@@ -2,9 +2,10 @@ use std::collections::HashMap;
 use std::fs;
 use std::ops::Range;
 use std::sync::{Arc, Mutex};
-use indicatif::{ProgressBar, ProgressStyle};
 use std::thread;
 
+use crate::verbosity::{info, progress_bar};
+
 #[derive(Debug, Clone)]
 struct Category {
     name: String,
@@ -87,7 +88,7 @@ fn find_location(seed: i64, mappers: &HashMap<String, Mapper>) -> i64 {
 
 fn puzzle_1() {
     let almanac = parse_input();
-    let bar = ProgressBar::new(almanac.seeds.len() as u64);
+    let bar = progress_bar(almanac.seeds.len() as u64);
     let mut locations: Vec<i64> = Vec::new();
 
     for seed in almanac.seeds {
@@ -104,9 +105,7 @@ fn puzzle_2() {
     let chunks = almanac.seeds.chunks(2);
     let ranges = chunks.map(|chunk| Range { start: chunk[0], end: chunk[0] + chunk[1] }).collect::<Vec<Range<i64>>>();
     let sum: i64 = ranges.iter().map(|range| range.end - range.start).collect::<Vec<_>>().iter().sum();
-    let pb = ProgressBar::new(sum as u64);
-    pb.set_style(ProgressStyle::with_template("[{elapsed_precise}] {wide_bar} {pos:>7}/{len:7} ({percent}%) remaining: ~{eta} {msg}")
-        .unwrap());
+    let pb = progress_bar(sum as u64);
     let pb = Arc::new(Mutex::new(pb));
 
     for range in ranges {
@@ -134,7 +133,7 @@ fn puzzle_2() {
         let result = i.join().unwrap();
         locations.push(result);
     }
-    println!("Location: {:?}", locations);
+    info!("Location: {:?}", locations);
     println!("Min location: {:?}", locations.iter().min())
     // Min location: Some(1081323768) - wrong
 
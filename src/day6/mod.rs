@@ -1,7 +1,8 @@
 use std::fs;
 use std::iter::zip;
 use std::time::Duration;
-use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::verbosity::progress_bar;
 
 #[derive(Debug)]
 struct Race {
@@ -83,9 +84,7 @@ fn puzzle_1() {
 fn puzzle_2() {
     let race = parse_input_for_single_race();
 
-    let pb = ProgressBar::new(race.time.as_millis() as u64);
-    pb.set_style(ProgressStyle::with_template("[{elapsed_precise}] {wide_bar} {pos:>7}/{len:7} ({percent}%) remaining: ~{eta} {msg}")
-        .unwrap());
+    let pb = progress_bar(race.time.as_millis() as u64);
 
     let mut beatable_ways: i32 = 0;
 
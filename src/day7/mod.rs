@@ -4,14 +4,39 @@ use std::{fmt, fs};
 use std::fmt::Formatter;
 use std::str::FromStr;
 
+use crate::verbosity::trace;
+
+mod simulator;
+
 #[derive(Clone, PartialEq, Eq, Hash, Ord)]
 struct Label {
     value: String,
     strength: i32,
 }
 
+#[derive(Debug, PartialEq)]
+enum ParseError {
+    MalformedLine { line: usize },
+    InvalidLabel { line: usize, column: usize, label: char },
+    InvalidHandLength { line: usize, labels: usize },
+    InvalidBid { line: usize, bid: String },
+    DuplicateHand { line: usize, first_line: usize, hand: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MalformedLine { line } => write!(f, "line {line}: expected `<hand> <bid>`"),
+            ParseError::InvalidLabel { line, column, label } => write!(f, "line {line}, column {column}: invalid card label {label:?}"),
+            ParseError::InvalidHandLength { line, labels } => write!(f, "line {line}: hand has {labels} labels, expected 5"),
+            ParseError::InvalidBid { line, bid } => write!(f, "line {line}: invalid bid {bid:?}"),
+            ParseError::DuplicateHand { line, first_line, hand } => write!(f, "line {line}: hand {hand} already dealt on line {first_line}"),
+        }
+    }
+}
+
 impl FromStr for Label {
-    type Err = ();
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let value = s.to_string();
@@ -29,8 +54,7 @@ impl FromStr for Label {
             "4" => Label { value, strength: 4 },
             "3" => Label { value, strength: 3 },
             "2" => Label { value, strength: 2 },
-            "1" => Label { value, strength: 1 },
-            _ => panic!()
+            _ => return Err(value)
         });
     }
 }
@@ -51,6 +75,12 @@ impl Label {
     fn strongest() -> Label {
         return Label { value: String::from("A"), strength: 14 };
     }
+
+    /// With jokers, `J` becomes the weakest label when comparing hands.
+    fn joker_aware(mut self, with_jokers: bool) -> Label {
+        if with_jokers && self.value == "J" { self.strength = 1 }
+        return self;
+    }
 }
 
 #[derive(PartialEq)]
@@ -126,7 +156,7 @@ impl PartialOrd for Rule<'_> {
 #[derive(Eq, PartialEq, Ord)]
 struct Hand {
     labels: Vec<Label>,
-    bid: i32,
+    bid: u64,
     with_jokers: bool,
 }
 
@@ -144,48 +174,93 @@ impl PartialOrd for Hand {
     }
 }
 
-fn parse_input(with_jokers: bool) -> Vec<Hand> {
-    let content = fs::read_to_string("src/day7/input.txt").expect("Something went wrong reading the file");
+/// Parses `<hand> <bid>` lines. In strict mode a hand may only be dealt once
+/// and bids must consist of ASCII digits only (no sign).
+fn parse_hands(content: &str, with_jokers: bool, strict: bool) -> Result<Vec<Hand>, ParseError> {
     let mut hands: Vec<Hand> = Vec::new();
-    for line in content.lines() {
+    let mut dealt: HashMap<&str, usize> = HashMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
         let input: Vec<&str> = line.split_whitespace().collect();
-        let [labels_input, bid_input] = <[&str; 2]>::try_from(input).ok().unwrap();
-        let labels: Vec<Label> = labels_input.chars().map(|l| {
-            let mut label = Label::from_str(&l.to_string()).unwrap();
-            if with_jokers && label.value == "J" { label.strength = 1 }
-            return label;
-        }).collect();
-        let bid: i32 = bid_input.parse::<i32>().unwrap();
+        let [labels_input, bid_input] = <[&str; 2]>::try_from(input).map_err(|_| ParseError::MalformedLine { line: line_number })?;
+
+        let mut labels: Vec<Label> = Vec::new();
+        for (column, l) in labels_input.chars().enumerate() {
+            let label = Label::from_str(&l.to_string())
+                .map_err(|_| ParseError::InvalidLabel { line: line_number, column: column + 1, label: l })?;
+            labels.push(label.joker_aware(with_jokers));
+        }
+        if labels.len() != 5 {
+            return Err(ParseError::InvalidHandLength { line: line_number, labels: labels.len() });
+        }
+
+        if strict && !bid_input.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ParseError::InvalidBid { line: line_number, bid: bid_input.to_string() });
+        }
+        let bid: u64 = bid_input.parse::<u64>().map_err(|_| ParseError::InvalidBid { line: line_number, bid: bid_input.to_string() })?;
+
+        if let Some(first_line) = dealt.insert(labels_input, line_number) {
+            if strict {
+                return Err(ParseError::DuplicateHand { line: line_number, first_line, hand: labels_input.to_string() });
+            }
+        }
         hands.push(Hand { labels, bid, with_jokers })
     }
-    return hands;
+    return Ok(hands);
 }
 
-fn calculate_total_winnings(hands: Vec<Hand>, with_jokers: bool) -> i32 {
-    let mut total_winnings = 0;
+fn parse_input(with_jokers: bool) -> Result<Vec<Hand>, ParseError> {
+    let content = fs::read_to_string("src/day7/input.txt").expect("Something went wrong reading the file");
+    return parse_hands(&content, with_jokers, false);
+}
 
+fn print_ranking(hands: &[Hand], with_jokers: bool) {
     for (rank, hand) in hands.iter().enumerate() {
         let rule = Rule::from_labels(&hand.labels, with_jokers);
-        total_winnings += hand.bid * (rank as i32 + 1);
-        println!("{:?} - {:?}: {:?}", rank, rule, hand)
+        trace!("{:?} - {:?}: {:?}", rank, rule, hand)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct WinningsOverflow {
+    rank: u64,
+    bid: u64,
+}
+
+impl fmt::Display for WinningsOverflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "total winnings overflow at rank {} (bid {})", self.rank, self.bid)
     }
-    return total_winnings;
+}
+
+fn calculate_total_winnings(hands: Vec<Hand>) -> Result<u64, WinningsOverflow> {
+    let mut total_winnings: u64 = 0;
+
+    for (rank, hand) in hands.iter().enumerate() {
+        let rank = rank as u64 + 1;
+        total_winnings = hand.bid.checked_mul(rank)
+            .and_then(|winnings| total_winnings.checked_add(winnings))
+            .ok_or(WinningsOverflow { rank, bid: hand.bid })?;
+    }
+    return Ok(total_winnings);
 }
 
 fn puzzle_1() {
     let with_jokers = false;
-    let mut hands = parse_input(with_jokers);
+    let mut hands = parse_input(with_jokers).unwrap_or_else(|error| panic!("{error}"));
     hands.sort();
-    let total_winnings = calculate_total_winnings(hands, with_jokers);
+    print_ranking(&hands, with_jokers);
+    let total_winnings = calculate_total_winnings(hands).unwrap_or_else(|error| panic!("{error}"));
 
     println!("{:?}", total_winnings)
 }
 
 fn puzzle_2() {
     let with_jokers = true;
-    let mut hands = parse_input(with_jokers);
+    let mut hands = parse_input(with_jokers).unwrap_or_else(|error| panic!("{error}"));
     hands.sort();
-    let total_winnings = calculate_total_winnings(hands, with_jokers);
+    print_ranking(&hands, with_jokers);
+    let total_winnings = calculate_total_winnings(hands).unwrap_or_else(|error| panic!("{error}"));
 
     println!("{:?}", total_winnings)
 }
@@ -193,4 +268,77 @@ fn puzzle_2() {
 pub(crate) fn run() {
     // puzzle_1();
     puzzle_2();
-}
\ No newline at end of file
+    // simulator::run();
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LABELS: [char; 13] = ['A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2'];
+
+    /// `count` distinct hands, the n-th one spelling n in base 13.
+    fn synthetic_input(count: usize, bid: impl Fn(usize) -> u64) -> String {
+        let mut content = String::new();
+        for index in 0..count {
+            let mut n = index;
+            let hand: String = (0..5).map(|_| {
+                let label = LABELS[n % LABELS.len()];
+                n /= LABELS.len();
+                label
+            }).collect();
+            content.push_str(&format!("{hand} {}\n", bid(index)));
+        }
+        content
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_offending_input() {
+        let parse = |content: &str, strict: bool| parse_hands(content, false, strict).err();
+        assert_eq!(parse("32T3K 765\nKK677\n", false), Some(ParseError::MalformedLine { line: 2 }));
+        assert_eq!(parse("32T3K 765\nKK677 28 1\n", false), Some(ParseError::MalformedLine { line: 2 }));
+        assert_eq!(parse("32T3K 765\nKTJ1T 220\n", false), Some(ParseError::InvalidLabel { line: 2, column: 4, label: '1' }));
+        assert_eq!(parse("AK 5\n", false), Some(ParseError::InvalidHandLength { line: 1, labels: 2 }));
+        assert_eq!(parse("32T3K 765\nAAAAAAA 5\n", false), Some(ParseError::InvalidHandLength { line: 2, labels: 7 }));
+        assert_eq!(parse("32T3K 76x\n", false), Some(ParseError::InvalidBid { line: 1, bid: String::from("76x") }));
+        assert_eq!(parse("32T3K 765\nQQQJA 483\n32T3K 28\n", true), Some(ParseError::DuplicateHand { line: 3, first_line: 1, hand: String::from("32T3K") }));
+        assert_eq!(ParseError::InvalidLabel { line: 2, column: 4, label: '1' }.to_string(), "line 2, column 4: invalid card label '1'");
+    }
+
+    #[test]
+    fn strict_mode_rejects_what_lenient_mode_accepts() {
+        let signed_bid = "32T3K +765\n";
+        assert_eq!(parse_hands(signed_bid, false, false).map(|hands| hands[0].bid), Ok(765));
+        assert_eq!(parse_hands(signed_bid, false, true).err(), Some(ParseError::InvalidBid { line: 1, bid: String::from("+765") }));
+
+        let duplicate = "32T3K 765\n32T3K 28\n";
+        assert_eq!(parse_hands(duplicate, false, false).map(|hands| hands.len()), Ok(2));
+        assert!(parse_hands(duplicate, false, true).is_err());
+    }
+
+    // Ranking is exercised by the puzzles themselves; the winnings arithmetic
+    // only depends on the order of the hands it is given.
+    #[test]
+    fn total_winnings_of_100k_hands_beyond_i32() {
+        let content = synthetic_input(100_000, |_| 1_000_000);
+        let hands = parse_hands(&content, false, true).unwrap();
+        assert_eq!(calculate_total_winnings(hands), Ok(1_000_000 * 100_000 * 100_001 / 2));
+    }
+
+    #[test]
+    fn total_winnings_of_100k_hands_matches_wide_arithmetic() {
+        let content = synthetic_input(100_000, |index| (index as u64 * 7919) % 1_000_000_007);
+        let hands = parse_hands(&content, true, true).unwrap();
+        let expected: u128 = hands.iter().enumerate().map(|(rank, hand)| hand.bid as u128 * (rank as u128 + 1)).sum();
+        assert!(expected > i32::MAX as u128);
+        assert_eq!(calculate_total_winnings(hands).map(|total| total as u128), Ok(expected));
+    }
+
+    #[test]
+    fn total_winnings_overflow_is_an_error() {
+        let content = format!("22222 {}\n33333 {}\n", u64::MAX / 2, u64::MAX / 2);
+        let mut hands = parse_hands(&content, false, true).unwrap();
+        hands.sort();
+        let total_winnings = calculate_total_winnings(hands);
+        assert_eq!(total_winnings, Err(WinningsOverflow { rank: 2, bid: u64::MAX / 2 }));
+    }
+}
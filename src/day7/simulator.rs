@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+use std::iter::repeat_n;
+use std::str::FromStr;
+
+use super::{calculate_total_winnings, Hand, Label, Rule};
+
+const HAND_TYPES: [(i32, &str); 7] = [
+    (7, "Five of a kind"),
+    (6, "Four of a kind"),
+    (5, "Full house"),
+    (4, "Three of a kind"),
+    (3, "Two pair"),
+    (2, "One pair"),
+    (1, "High card"),
+];
+
+/// xorshift64* generator, so a simulation is reproducible from its seed.
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        // An all-zero state would only ever produce zeros.
+        return Rng { state: seed.max(1) };
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        return x.wrapping_mul(0x2545F4914F6CDD1D);
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        return self.next() % bound;
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Deck {
+    labels: Vec<Label>,
+    /// Copies of every label in the deck; `None` deals from an endless supply.
+    copies: Option<usize>,
+}
+
+impl Deck {
+    fn new(labels: &str, copies: Option<usize>) -> Result<Deck, String> {
+        let labels = labels.chars().map(|l| Label::from_str(&l.to_string())).collect::<Result<Vec<Label>, String>>()
+            .map_err(|label| format!("invalid card label {label:?} in deck"))?;
+        if labels.is_empty() {
+            return Err(String::from("deck has no labels"));
+        }
+        // `expected_frequencies` assumes every label is equally likely.
+        if let Some((index, _)) = labels.iter().enumerate().find(|(index, label)| labels[..*index].contains(label)) {
+            return Err(format!("card label {:?} appears twice in deck", labels[index].value));
+        }
+        return Ok(Deck { labels, copies });
+    }
+
+    fn deal(&self, rng: &mut Rng, hands: usize, with_jokers: bool) -> Result<Vec<Vec<Label>>, String> {
+        let cards = hands * 5;
+        let dealt: Vec<&Label> = match self.copies {
+            None => (0..cards).map(|_| &self.labels[rng.below(self.labels.len() as u64) as usize]).collect(),
+            Some(copies) => {
+                let mut pile: Vec<&Label> = self.labels.iter().flat_map(|label| repeat_n(label, copies)).collect();
+                if cards > pile.len() {
+                    return Err(format!("cannot deal {hands} hands from a deck of {} cards", pile.len()));
+                }
+                for i in 0..cards {
+                    let j = i + rng.below((pile.len() - i) as u64) as usize;
+                    pile.swap(i, j);
+                }
+                pile.truncate(cards);
+                pile
+            }
+        };
+        return Ok(dealt.chunks(5).map(|hand| hand.iter().map(|&label| label.clone().joker_aware(with_jokers)).collect()).collect());
+    }
+}
+
+struct Simulation {
+    deck: Deck,
+    players: usize,
+    rounds: usize,
+    max_bid: u64,
+    seed: u64,
+}
+
+#[derive(Debug)]
+struct Report {
+    with_jokers: bool,
+    hands: u64,
+    /// Dealt hands per rule strength.
+    frequencies: BTreeMap<i32, u64>,
+    /// Total winnings of every round.
+    winnings: Vec<u64>,
+}
+
+impl Simulation {
+    fn run(&self, with_jokers: bool) -> Result<Report, String> {
+        if self.max_bid == 0 {
+            return Err(String::from("maximum bid must be at least 1"));
+        }
+        let mut rng = Rng::new(self.seed);
+        let mut report = Report { with_jokers, hands: 0, frequencies: BTreeMap::new(), winnings: Vec::new() };
+
+        for _ in 0..self.rounds {
+            let mut hands: Vec<Hand> = Vec::new();
+            for labels in self.deck.deal(&mut rng, self.players, with_jokers)? {
+                let rule = Rule::from_labels(&labels, with_jokers);
+                *report.frequencies.entry(rule.strength).or_insert(0) += 1;
+                report.hands += 1;
+                let bid = 1 + rng.below(self.max_bid);
+                hands.push(Hand { labels, bid, with_jokers });
+            }
+            hands.sort();
+            report.winnings.push(calculate_total_winnings(hands).map_err(|error| error.to_string())?);
+        }
+        return Ok(report);
+    }
+}
+
+fn binomial(n: u128, k: u128) -> u128 {
+    if k > n { return 0; }
+    return (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1));
+}
+
+fn factorial(n: u128) -> u128 {
+    return (1..=n).product();
+}
+
+fn partitions(remaining: usize, largest: usize, current: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+    if remaining == 0 {
+        out.push(current.clone());
+        return;
+    }
+    for part in (1..=remaining.min(largest)).rev() {
+        current.push(part);
+        partitions(remaining - part, part, current, out);
+        current.pop();
+    }
+}
+
+/// Hand strength from label multiplicities sorted in descending order, jokers joining the largest group.
+fn classify(parts: &[usize], jokers: usize) -> i32 {
+    let mut counts = parts.to_vec();
+    if counts.is_empty() { counts.push(0) }
+    counts[0] += jokers;
+    return match counts.as_slice() {
+        [5] => 7,
+        [4, ..] => 6,
+        [3, 2] => 5,
+        [3, ..] => 4,
+        [2, 2, ..] => 3,
+        [2, ..] => 2,
+        _ => 1,
+    };
+}
+
+/// Exact probability of every hand type for five cards dealt from `deck`.
+/// Counts label multiplicity patterns combinatorially instead of going through
+/// `Rule`, so it can be used to validate the classifier.
+fn expected_frequencies(deck: &Deck, with_jokers: bool) -> BTreeMap<i32, f64> {
+    let has_jokers = with_jokers && deck.labels.iter().any(|label| label.value == "J");
+    let labels = deck.labels.len() as u128;
+    let plain_labels = if has_jokers { labels - 1 } else { labels };
+    let max_jokers = if has_jokers { 5 } else { 0 };
+
+    let mut counts: BTreeMap<i32, u128> = BTreeMap::new();
+    for jokers in 0..=max_jokers {
+        let mut patterns: Vec<Vec<usize>> = Vec::new();
+        partitions(5 - jokers, 5 - jokers, &mut Vec::new(), &mut patterns);
+        for parts in patterns {
+            let groups = parts.len() as u128;
+            if groups > plain_labels { continue; }
+
+            // Distinct labels for the groups; groups of equal size are interchangeable.
+            let mut assignments = (0..groups).map(|i| plain_labels - i).product::<u128>();
+            for size in 1..=5 {
+                assignments /= factorial(parts.iter().filter(|&&part| part == size).count() as u128);
+            }
+
+            let ways = match deck.copies {
+                None => {
+                    let arrangements = factorial(5) / (factorial(jokers as u128) * parts.iter().map(|&part| factorial(part as u128)).product::<u128>());
+                    arrangements * assignments
+                }
+                Some(copies) => {
+                    let copies = copies as u128;
+                    binomial(copies, jokers as u128) * parts.iter().map(|&part| binomial(copies, part as u128)).product::<u128>() * assignments
+                }
+            };
+            *counts.entry(classify(&parts, jokers)).or_insert(0) += ways;
+        }
+    }
+
+    let total = match deck.copies {
+        None => labels.pow(5),
+        Some(copies) => binomial(labels * copies as u128, 5),
+    };
+    return counts.into_iter().map(|(strength, count)| (strength, count as f64 / total as f64)).collect();
+}
+
+fn print_report(report: &Report, expected: &BTreeMap<i32, f64>) {
+    let jokers = if report.with_jokers { "with jokers" } else { "without jokers" };
+    println!("{} hands in {} rounds, {jokers}", report.hands, report.winnings.len());
+    println!("{:<16} {:>10} {:>10} {:>10}", "Type", "Dealt", "Observed", "Expected");
+    for (strength, name) in HAND_TYPES {
+        let dealt = report.frequencies.get(&strength).copied().unwrap_or(0);
+        let observed = 100.0 * dealt as f64 / report.hands.max(1) as f64;
+        let expected = 100.0 * expected.get(&strength).copied().unwrap_or(0.0);
+        println!("{name:<16} {dealt:>10} {observed:>9.4}% {expected:>9.4}%");
+    }
+
+    let winnings = &report.winnings;
+    if let (Some(min), Some(max)) = (winnings.iter().min(), winnings.iter().max()) {
+        let mean = winnings.iter().map(|&w| w as f64).sum::<f64>() / winnings.len() as f64;
+        println!("Total winnings per round: min {min}, max {max}, mean {mean:.1}");
+    }
+}
+
+pub(super) fn run() {
+    let simulation = Simulation {
+        deck: Deck::new("AKQJT98765432", None).unwrap_or_else(|error| panic!("{error}")),
+        players: 1000,
+        rounds: 100,
+        max_bid: 1000,
+        seed: 2023,
+    };
+    for with_jokers in [false, true] {
+        let report = simulation.run(with_jokers).unwrap_or_else(|error| panic!("{error}"));
+        print_report(&report, &expected_frequencies(&simulation.deck, with_jokers));
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LABELS: &str = "AKQJT98765432";
+
+    #[test]
+    fn classifier_matches_combinatorial_probabilities() {
+        let deck = Deck::new(LABELS, None).unwrap();
+        for with_jokers in [false, true] {
+            let mut counts: BTreeMap<i32, u64> = BTreeMap::new();
+            let mut hand: Vec<Label> = Vec::with_capacity(5);
+            for index in 0..deck.labels.len().pow(5) {
+                hand.clear();
+                let mut n = index;
+                for _ in 0..5 {
+                    hand.push(deck.labels[n % deck.labels.len()].clone().joker_aware(with_jokers));
+                    n /= deck.labels.len();
+                }
+                *counts.entry(Rule::from_labels(&hand, with_jokers).strength).or_insert(0) += 1;
+            }
+
+            let total = deck.labels.len().pow(5) as f64;
+            let expected = expected_frequencies(&deck, with_jokers);
+            assert_eq!(counts.keys().collect::<Vec<&i32>>(), expected.keys().collect::<Vec<&i32>>());
+            for (strength, count) in counts {
+                assert!((count as f64 / total - expected[&strength]).abs() < 1e-12, "strength {strength}, jokers {with_jokers}");
+            }
+        }
+    }
+
+    #[test]
+    fn finite_deck_distribution_sums_to_one() {
+        let deck = Deck::new(LABELS, Some(4)).unwrap();
+        for with_jokers in [false, true] {
+            let total: f64 = expected_frequencies(&deck, with_jokers).values().sum();
+            assert!((total - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_decks_and_bids() {
+        assert_eq!(Deck::new("AAK", None).err(), Some(String::from("card label \"A\" appears twice in deck")));
+        assert!(Deck::new("", None).is_err());
+        assert!(Deck::new("AX", None).is_err());
+
+        let simulation = Simulation { deck: Deck::new(LABELS, None).unwrap(), players: 10, rounds: 1, max_bid: 0, seed: 1 };
+        assert_eq!(simulation.run(false).err(), Some(String::from("maximum bid must be at least 1")));
+        assert_eq!(Simulation { max_bid: 1, ..simulation }.run(false).map(|report| report.hands), Ok(10));
+    }
+
+    #[test]
+    fn deal_refuses_more_cards_than_the_deck_holds() {
+        let deck = Deck::new("AK", Some(5)).unwrap();
+        let mut rng = Rng::new(1);
+        assert_eq!(deck.deal(&mut rng, 2, false).map(|hands| hands.len()), Ok(2));
+        assert_eq!(deck.deal(&mut rng, 3, false), Err(String::from("cannot deal 3 hands from a deck of 10 cards")));
+    }
+}
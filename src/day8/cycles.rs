@@ -0,0 +1,152 @@
+use std::collections::BTreeSet;
+
+use crate::math::{crt, lcm_all, CrtError};
+
+use super::{matching_nodes, Network, NodeId, NodePattern, WalkError};
+
+/// Steps at which a single ghost sits on an end node. A walk is a sequence of
+/// (node, instruction index) states, so it must eventually repeat: after
+/// `prefix` steps the ghost enters a cycle of `length` steps.
+#[derive(Debug, PartialEq)]
+struct Cycle {
+    prefix: u64,
+    length: u64,
+    /// End-node hits before the cycle starts; they never repeat.
+    transient_hits: Vec<u64>,
+    /// End-node hits within the first lap of the cycle, in `prefix..prefix + length`.
+    /// Each one repeats every `length` steps.
+    cyclic_hits: Vec<u64>,
+}
+
+impl Cycle {
+    fn hits(&self, step: u64) -> bool {
+        if step < self.prefix {
+            return self.transient_hits.contains(&step);
+        }
+        let lap_step = self.prefix + (step - self.prefix) % self.length;
+        return self.cyclic_hits.contains(&lap_step);
+    }
+}
+
+fn analyse(network: &Network, start: NodeId, ends: &NodePattern) -> Cycle {
+    let instructions = network.instructions.len();
+    // First step at which each (node, instruction index) state was visited.
+    let mut seen: Vec<Option<u64>> = vec![None; network.names.len() * instructions];
+    let mut hits: Vec<u64> = Vec::new();
+    let mut current_node = start;
+    let mut step: u64 = 0;
+
+    loop {
+        let instruction_index = step as usize % instructions;
+        let state = current_node as usize * instructions + instruction_index;
+        if let Some(prefix) = seen[state] {
+            let (transient_hits, cyclic_hits) = hits.into_iter().partition(|&hit| hit < prefix);
+            return Cycle { prefix, length: step - prefix, transient_hits, cyclic_hits };
+        }
+        seen[state] = Some(step);
+        if ends.matches(network.name(current_node)) {
+            hits.push(step);
+        }
+
+        current_node = network.next(current_node, &network.instructions[instruction_index]);
+        step += 1;
+    }
+}
+
+/// First step at which every ghost sits on an end node at once, `None` if they never do.
+fn synchronise(cycles: &[Cycle]) -> Option<u128> {
+    let first = cycles.first()?;
+    let settled = cycles.iter().map(|cycle| cycle.prefix).max().unwrap();
+
+    // Before every ghost has entered its cycle only explicit hits can line up.
+    let mut early: BTreeSet<u64> = first.transient_hits.iter().copied().collect();
+    for &hit in &first.cyclic_hits {
+        early.extend((hit..settled).step_by(first.length as usize));
+    }
+    if let Some(&step) = early.iter().find(|&&step| step < settled && cycles.iter().all(|cycle| cycle.hits(step))) {
+        return Some(step as u128);
+    }
+
+    // The usual puzzle shape: every ghost is on an end node exactly at the
+    // multiples of its cycle length, so they meet at multiples of the lcm.
+    if cycles.iter().all(|cycle| matches!(cycle.cyclic_hits[..], [hit] if hit % cycle.length == 0)) {
+        if let Some(period) = lcm_all(cycles.iter().map(|cycle| cycle.length as u128)) {
+            return Some((settled as u128).div_ceil(period) * period);
+        }
+    }
+
+    // Afterwards each ghost is on an end node exactly when the step matches one of
+    // its cyclic hits modulo its cycle length.
+    let mut congruences: BTreeSet<(u128, u128)> = BTreeSet::from([(0, 1)]);
+    for cycle in cycles {
+        let mut merged: BTreeSet<(u128, u128)> = BTreeSet::new();
+        for &congruence in &congruences {
+            for &hit in &cycle.cyclic_hits {
+                match crt(congruence, (hit as u128, cycle.length as u128)) {
+                    Ok(solution) => { merged.insert(solution); }
+                    Err(CrtError::Incompatible) => {}
+                    Err(CrtError::Overflow) => panic!("Combined cycle length does not fit in u128"),
+                }
+            }
+        }
+        congruences = merged;
+    }
+
+    let settled = settled as u128;
+    return congruences.iter().map(|&(residue, modulus)| {
+        if residue >= settled { residue } else { residue + (settled - residue).div_ceil(modulus) * modulus }
+    }).min();
+}
+
+/// Steps until ghosts starting on every node matching `starts` stand on nodes
+/// matching `ends` simultaneously, `None` if that never happens.
+pub(super) fn synchronised_steps(network: &Network, starts: &NodePattern, ends: &NodePattern) -> Result<Option<u128>, WalkError> {
+    let cycles: Vec<Cycle> = matching_nodes(network, starts)?.into_iter()
+        .map(|node| analyse(network, node, ends))
+        .collect();
+    return Ok(synchronise(&cycles));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day8::parse_network;
+
+    fn ghost_steps(network: &Network) -> Option<u128> {
+        synchronised_steps(network, &NodePattern::Suffix(String::from("A")), &NodePattern::Suffix(String::from("Z"))).unwrap()
+    }
+
+    #[test]
+    fn example_network() {
+        let network = parse_network("LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)\n").unwrap();
+        assert_eq!(ghost_steps(&network), Some(6));
+    }
+
+    #[test]
+    fn cycles_with_a_prefix_and_unrelated_first_hits() {
+        // Ghost 1 is on Z at 2, 4, 6, ...; ghost 2 at 1, 4, 7, ...
+        let network = parse_network("L\n\n1A = (1B, 1B)\n1B = (1Z, 1Z)\n1Z = (1C, 1C)\n1C = (1Z, 1Z)\n2A = (2Z, 2Z)\n2Z = (2C, 2C)\n2C = (2D, 2D)\n2D = (2Z, 2Z)\n").unwrap();
+        assert_eq!(analyse(&network, network.id("1A").unwrap(), &NodePattern::Suffix(String::from("Z"))), Cycle { prefix: 2, length: 2, transient_hits: vec![], cyclic_hits: vec![2] });
+        assert_eq!(ghost_steps(&network), Some(4));
+    }
+
+    #[test]
+    fn synchronised_before_entering_cycles() {
+        let network = parse_network("L\n\n1A = (1Z, 1Z)\n1Z = (1B, 1B)\n1B = (1B, 1B)\n2A = (2Z, 2Z)\n2Z = (2B, 2B)\n2B = (2C, 2C)\n2C = (2B, 2B)\n").unwrap();
+        assert_eq!(ghost_steps(&network), Some(1));
+    }
+
+    #[test]
+    fn hits_at_multiples_of_cycle_lengths() {
+        // Ghost 1 is on Z at 2, 4, 6, ...; ghost 2 at 3, 6, 9, ...
+        let network = parse_network("L\n\n1A = (1B, 1B)\n1B = (1Z, 1Z)\n1Z = (1B, 1B)\n2A = (2B, 2B)\n2B = (2C, 2C)\n2C = (2Z, 2Z)\n2Z = (2B, 2B)\n").unwrap();
+        assert_eq!(analyse(&network, network.id("2A").unwrap(), &NodePattern::Suffix(String::from("Z"))), Cycle { prefix: 1, length: 3, transient_hits: vec![], cyclic_hits: vec![3] });
+        assert_eq!(ghost_steps(&network), Some(6));
+    }
+
+    #[test]
+    fn ghosts_that_never_synchronise() {
+        let network = parse_network("L\n\n1A = (1Z, 1Z)\n1Z = (1B, 1B)\n1B = (1Z, 1Z)\n2A = (2B, 2B)\n2B = (2Z, 2Z)\n2Z = (2B, 2B)\n").unwrap();
+        assert_eq!(ghost_steps(&network), None);
+    }
+}
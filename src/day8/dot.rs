@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+
+use super::{Instruction, Network, NodeId};
+
+/// Edges taken by walking `steps` steps from `start`.
+fn walked_edges(network: &Network, start: NodeId, steps: u64) -> HashSet<(NodeId, Instruction)> {
+    let mut edges: HashSet<(NodeId, Instruction)> = HashSet::new();
+    let mut current_node = start;
+    for instruction in network.instructions.iter().cycle().take(steps as usize) {
+        edges.insert((current_node, *instruction));
+        current_node = network.next(current_node, instruction);
+    }
+    return edges;
+}
+
+/// Renders the network as a Graphviz digraph. `..A` nodes are drawn green,
+/// `..Z` nodes red, and the edges of `walked` (start node, steps) in blue.
+fn to_dot(network: &Network, walked: Option<(NodeId, u64)>) -> String {
+    let walked = walked.map(|(start, steps)| walked_edges(network, start, steps)).unwrap_or_default();
+    let mut dot = String::from("digraph network {\n");
+
+    for node in network.node_ids() {
+        let name = network.name(node);
+        let style = if name.ends_with('A') {
+            " [style=filled, fillcolor=palegreen]"
+        } else if name.ends_with('Z') {
+            " [style=filled, fillcolor=salmon]"
+        } else {
+            ""
+        };
+        writeln!(dot, "    \"{name}\"{style};").unwrap();
+    }
+
+    for node in network.node_ids() {
+        for (instruction, label) in [(Instruction::Left, "L"), (Instruction::Right, "R")] {
+            let style = if walked.contains(&(node, instruction)) { ", color=blue, penwidth=2" } else { "" };
+            let next = network.next(node, &instruction);
+            writeln!(dot, "    \"{}\" -> \"{}\" [label={label}{style}];", network.name(node), network.name(next)).unwrap();
+        }
+    }
+
+    dot.push_str("}\n");
+    return dot;
+}
+
+/// Writes the network to `path`, optionally highlighting a walk of `steps` steps from a start node.
+pub(super) fn export(network: &Network, path: &str, walk: Option<(&str, u64)>) {
+    let walked = walk.map(|(start, steps)| (network.id(start).expect("Start node is not defined in the network"), steps));
+    fs::write(path, to_dot(network, walked)).expect("Something went wrong writing the file");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day8::parse_network;
+
+    #[test]
+    fn highlights_start_end_and_walk() {
+        let network = parse_network("L\n\nAAA = (BBB, ZZZ)\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        let dot = to_dot(&network, Some((network.id("AAA").unwrap(), 2)));
+        assert!(dot.contains("\"AAA\" [style=filled, fillcolor=palegreen];"));
+        assert!(dot.contains("\"BBB\";"));
+        assert!(dot.contains("\"ZZZ\" [style=filled, fillcolor=salmon];"));
+        assert!(dot.contains("\"AAA\" -> \"BBB\" [label=L, color=blue, penwidth=2];"));
+        assert!(dot.contains("\"AAA\" -> \"ZZZ\" [label=R];"));
+        assert!(dot.contains("\"BBB\" -> \"ZZZ\" [label=L, color=blue, penwidth=2];"));
+        assert!(dot.contains("\"ZZZ\" -> \"ZZZ\" [label=L];"));
+    }
+}
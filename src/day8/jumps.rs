@@ -0,0 +1,106 @@
+use super::{matching_nodes, Instruction, Network, NodeId, NodePattern};
+
+/// Length of the shortest block the instruction string is a repetition of.
+fn minimal_period(instructions: &[Instruction]) -> usize {
+    // Prefix function: longest proper prefix that is also a suffix of instructions[..=i].
+    let mut prefix: Vec<usize> = vec![0; instructions.len()];
+    for i in 1..instructions.len() {
+        let mut k = prefix[i - 1];
+        while k > 0 && instructions[i] != instructions[k] {
+            k = prefix[k - 1];
+        }
+        if instructions[i] == instructions[k] {
+            k += 1;
+        }
+        prefix[i] = k;
+    }
+
+    let length = instructions.len();
+    let period = length - prefix.last().copied().unwrap_or(0);
+    return if length.is_multiple_of(period) { period } else { length };
+}
+
+/// `levels[k][node]` is where a ghost standing on `node` at the start of the
+/// instruction string ends up after 2^k passes over its minimal period.
+struct JumpTable {
+    period: usize,
+    levels: Vec<Vec<NodeId>>,
+}
+
+impl JumpTable {
+    fn new(network: &Network) -> JumpTable {
+        let period = minimal_period(&network.instructions);
+        let pass: Vec<NodeId> = network.node_ids().map(|node| {
+            network.instructions[..period].iter().fold(node, |current, instruction| network.next(current, instruction))
+        }).collect();
+
+        let max_passes = u64::MAX / period as u64;
+        let mut levels = vec![pass];
+        while 1u64.checked_shl(levels.len() as u32).is_some_and(|passes| passes <= max_passes) {
+            let previous = levels.last().unwrap();
+            let doubled = previous.iter().map(|&node| previous[node as usize]).collect();
+            levels.push(doubled);
+        }
+        return JumpTable { period, levels };
+    }
+
+    /// Node reached after `steps` steps from `start` in O(log steps + period).
+    fn position(&self, network: &Network, start: NodeId, steps: u64) -> NodeId {
+        let passes = steps / self.period as u64;
+        let remainder = (steps % self.period as u64) as usize;
+
+        let mut current = start;
+        for (level, jumps) in self.levels.iter().enumerate() {
+            if passes >> level & 1 == 1 {
+                current = jumps[current as usize];
+            }
+        }
+        // The period divides the instruction string, so the next pass starts at its beginning.
+        return network.instructions[..remainder].iter().fold(current, |current, instruction| network.next(current, instruction));
+    }
+}
+
+pub(super) fn run(network: &Network, steps: u64) {
+    let table = JumpTable::new(network);
+    println!("Instruction period: {} of {}", table.period, network.instructions.len());
+    let ghosts = matching_nodes(network, &NodePattern::Suffix(String::from("A"))).unwrap_or_else(|error| panic!("{error}"));
+    for (ghost, start) in ghosts.into_iter().enumerate() {
+        let position = table.position(network, start, steps);
+        println!("Ghost {ghost} from {} is on {} after {steps} steps", network.name(start), network.name(position));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day8::parse_network;
+
+    fn instructions(input: &str) -> Vec<Instruction> {
+        input.chars().map(|c| if c == 'L' { Instruction::Left } else { Instruction::Right }).collect()
+    }
+
+    #[test]
+    fn minimal_period_of_instruction_strings() {
+        assert_eq!(minimal_period(&instructions("LLLL")), 1);
+        assert_eq!(minimal_period(&instructions("LRLRLR")), 2);
+        assert_eq!(minimal_period(&instructions("LRRLRRLRR")), 3);
+        assert_eq!(minimal_period(&instructions("LRL")), 3);
+        assert_eq!(minimal_period(&instructions("LRRLR")), 5);
+    }
+
+    #[test]
+    fn jumps_match_step_by_step_walk() {
+        let network = parse_network("LRLLRL\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, 22A)\n").unwrap();
+        let table = JumpTable::new(&network);
+        assert_eq!(table.period, 3);
+        for start in network.node_ids() {
+            let mut current = start;
+            for (steps, instruction) in network.instructions.iter().cycle().take(200).enumerate() {
+                assert_eq!(table.position(&network, start, steps as u64), current);
+                current = network.next(current, instruction);
+            }
+        }
+        let start = network.id("22A").unwrap();
+        assert_eq!(network.name(table.position(&network, start, 1_000_000_000_000_000)), "22B");
+    }
+}
@@ -0,0 +1,144 @@
+use std::fs;
+use std::path::Path;
+
+use indicatif::ProgressBar;
+
+use crate::verbosity::progress_bar;
+
+use super::{matching_nodes, Network, NodeId, NodePattern};
+
+const PROGRESS_INTERVAL: u64 = 1_000_000;
+
+/// State of a lockstep simulation: every ghost's position after `steps` steps.
+#[derive(Debug, Clone, PartialEq)]
+struct Checkpoint {
+    steps: u64,
+    positions: Vec<NodeId>,
+}
+
+impl Checkpoint {
+    fn start(network: &Network, starts: &NodePattern) -> Result<Checkpoint, String> {
+        let positions = matching_nodes(network, starts).map_err(|error| error.to_string())?;
+        return Ok(Checkpoint { steps: 0, positions });
+    }
+
+    /// Step count on the first line, node names on the second, so a checkpoint
+    /// stays valid however the network happens to be interned.
+    fn serialise(&self, network: &Network) -> String {
+        let names: Vec<&str> = self.positions.iter().map(|&node| network.name(node)).collect();
+        return format!("{}\n{}\n", self.steps, names.join(" "));
+    }
+
+    /// Fails unless the checkpoint holds one position per node matching `starts`.
+    fn deserialise(content: &str, network: &Network, starts: &NodePattern) -> Result<Checkpoint, String> {
+        let mut lines = content.lines();
+        let steps = lines.next().and_then(|steps| steps.trim().parse::<u64>().ok())
+            .ok_or(String::from("checkpoint does not start with a step count"))?;
+        let positions = lines.next().unwrap_or("").split_whitespace()
+            .map(|name| network.id(name).ok_or(format!("checkpoint refers to unknown node {name}")))
+            .collect::<Result<Vec<NodeId>, String>>()?;
+        if positions.is_empty() {
+            return Err(String::from("checkpoint has no ghost positions"));
+        }
+        let ghosts = matching_nodes(network, starts).map_err(|error| error.to_string())?.len();
+        if positions.len() != ghosts {
+            return Err(format!("checkpoint has {} ghost positions, expected {ghosts}", positions.len()));
+        }
+        return Ok(Checkpoint { steps, positions });
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Synchronised(u64),
+    /// The step budget ran out; the checkpoint resumes where the simulation stopped.
+    BudgetExhausted(Checkpoint),
+}
+
+/// Moves all ghosts one step at a time until they all stand on nodes matching
+/// `ends`, or until `budget` more steps have been taken.
+fn simulate(network: &Network, mut checkpoint: Checkpoint, ends: &NodePattern, budget: u64, progress: &ProgressBar) -> Outcome {
+    let is_end: Vec<bool> = network.node_ids().map(|node| ends.matches(network.name(node))).collect();
+    let instructions = network.instructions.len() as u64;
+    let stop = checkpoint.steps.saturating_add(budget);
+
+    while !checkpoint.positions.iter().all(|&node| is_end[node as usize]) {
+        if checkpoint.steps == stop {
+            return Outcome::BudgetExhausted(checkpoint);
+        }
+        let instruction = &network.instructions[(checkpoint.steps % instructions) as usize];
+        for position in checkpoint.positions.iter_mut() {
+            *position = network.next(*position, instruction);
+        }
+        checkpoint.steps += 1;
+        if checkpoint.steps.is_multiple_of(PROGRESS_INTERVAL) {
+            progress.inc(PROGRESS_INTERVAL);
+        }
+    }
+    return Outcome::Synchronised(checkpoint.steps);
+}
+
+/// Simulates the ghosts for at most `budget` steps, resuming from and saving to `checkpoint_path`.
+pub(super) fn run(network: &Network, budget: u64, checkpoint_path: &str) {
+    let starts = NodePattern::Suffix(String::from("A"));
+    let ends = NodePattern::Suffix(String::from("Z"));
+    let checkpoint = if Path::new(checkpoint_path).exists() {
+        let content = fs::read_to_string(checkpoint_path).expect("Something went wrong reading the checkpoint");
+        Checkpoint::deserialise(&content, network, &starts)
+    } else {
+        Checkpoint::start(network, &starts)
+    }.unwrap_or_else(|error| panic!("{error}"));
+
+    let pb = progress_bar(budget);
+    let outcome = simulate(network, checkpoint, &ends, budget, &pb);
+    pb.finish_and_clear();
+
+    match outcome {
+        Outcome::Synchronised(steps) => println!("{steps}"),
+        Outcome::BudgetExhausted(checkpoint) => {
+            fs::write(checkpoint_path, checkpoint.serialise(network)).expect("Something went wrong writing the checkpoint");
+            println!("Not synchronised after {} steps, saved to {checkpoint_path}", checkpoint.steps);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day8::parse_network;
+
+    const EXAMPLE: &str = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)\n";
+
+    #[test]
+    fn ghosts_synchronise_in_lockstep() {
+        let network = parse_network(EXAMPLE).unwrap();
+        let ends = NodePattern::Suffix(String::from("Z"));
+        let checkpoint = Checkpoint::start(&network, &NodePattern::Suffix(String::from("A"))).unwrap();
+        assert_eq!(simulate(&network, checkpoint, &ends, 100, &ProgressBar::hidden()), Outcome::Synchronised(6));
+    }
+
+    #[test]
+    fn resumes_from_checkpoint() {
+        let network = parse_network(EXAMPLE).unwrap();
+        let ends = NodePattern::Suffix(String::from("Z"));
+        let checkpoint = Checkpoint::start(&network, &NodePattern::Suffix(String::from("A"))).unwrap();
+
+        let Outcome::BudgetExhausted(checkpoint) = simulate(&network, checkpoint, &ends, 4, &ProgressBar::hidden()) else { panic!() };
+        let saved = checkpoint.serialise(&network);
+        assert_eq!(saved, "4\n11Z 22B\n");
+
+        let checkpoint = Checkpoint::deserialise(&saved, &network, &NodePattern::Suffix(String::from("A"))).unwrap();
+        assert_eq!(simulate(&network, checkpoint, &ends, 4, &ProgressBar::hidden()), Outcome::Synchronised(6));
+    }
+
+    #[test]
+    fn rejects_corrupt_checkpoints() {
+        let network = parse_network(EXAMPLE).unwrap();
+        let starts = NodePattern::Suffix(String::from("A"));
+        assert_eq!(Checkpoint::deserialise("4\n", &network, &starts), Err(String::from("checkpoint has no ghost positions")));
+        assert_eq!(Checkpoint::deserialise("4\n \n", &network, &starts), Err(String::from("checkpoint has no ghost positions")));
+        assert_eq!(Checkpoint::deserialise("4\n11Z\n", &network, &starts), Err(String::from("checkpoint has 1 ghost positions, expected 2")));
+        assert_eq!(Checkpoint::deserialise("4\n11Z 22B YYY\n", &network, &starts), Err(String::from("checkpoint refers to unknown node YYY")));
+        assert!(Checkpoint::deserialise("four\n11Z 22B\n", &network, &starts).is_err());
+    }
+}
@@ -1,103 +1,338 @@
 use std::collections::HashMap;
-use std::fs;
+use std::{fmt, fs};
+use std::fmt::Formatter;
 use std::str::FromStr;
 
+mod cycles;
+mod dot;
+mod jumps;
+mod lockstep;
+mod shortest;
 
-#[derive(Debug)]
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Instruction {
     Left,
     Right,
 }
 
 impl FromStr for Instruction {
-    type Err = ();
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         return Ok(match s {
             "L" => Instruction::Left,
             "R" => Instruction::Right,
-            _ => panic!()
+            _ => return Err(s.to_string())
         });
     }
 }
 
+#[derive(Debug, PartialEq)]
+enum ParseError {
+    MissingSection,
+    NoInstructions,
+    InvalidInstruction { line: usize, column: usize, instruction: char },
+    MalformedNode { line: usize },
+    UndefinedNode { line: usize, node: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingSection => write!(f, "expected instructions and nodes separated by an empty line"),
+            ParseError::NoInstructions => write!(f, "instruction list is empty"),
+            ParseError::InvalidInstruction { line, column, instruction } => write!(f, "line {line}, column {column}: invalid instruction {instruction:?}"),
+            ParseError::MalformedNode { line } => write!(f, "line {line}: expected `AAA = (BBB, CCC)`"),
+            ParseError::UndefinedNode { line, node } => write!(f, "line {line}: node {node} is never defined"),
+        }
+    }
+}
+
+type NodeId = u32;
+
+/// Nodes are interned in definition order; `left[id]` and `right[id]` hold
+/// the ids the node leads to, so walking never touches the names.
 #[derive(Debug)]
 struct Network {
     instructions: Vec<Instruction>,
-    nodes: HashMap<String, (String, String)>,
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    left: Vec<NodeId>,
+    right: Vec<NodeId>,
 }
 
-fn parse_input() -> Network {
-    let content = fs::read_to_string("src/day8/input.txt").expect("Something went wrong reading the file");
+impl Network {
+    fn id(&self, name: &str) -> Option<NodeId> {
+        return self.ids.get(name).copied();
+    }
+
+    fn name(&self, id: NodeId) -> &str {
+        return &self.names[id as usize];
+    }
+
+    fn node_ids(&self) -> impl Iterator<Item=NodeId> {
+        return 0..self.names.len() as NodeId;
+    }
+
+    fn next(&self, id: NodeId, instruction: &Instruction) -> NodeId {
+        return match instruction {
+            Instruction::Left => self.left[id as usize],
+            Instruction::Right => self.right[id as usize]
+        };
+    }
+}
+
+fn parse_network(content: &str) -> Result<Network, ParseError> {
     let chunks = content.split("\n\n").collect::<Vec<&str>>();
-    let [instructions_input, nodes_input] = <[&str; 2]>::try_from(chunks).ok().unwrap();
+    let [instructions_input, nodes_input] = <[&str; 2]>::try_from(chunks).map_err(|_| ParseError::MissingSection)?;
+
+    let mut instructions: Vec<Instruction> = Vec::new();
+    for (column, c) in instructions_input.trim_end().chars().enumerate() {
+        let instruction = Instruction::from_str(&c.to_string())
+            .map_err(|_| ParseError::InvalidInstruction { line: 1, column: column + 1, instruction: c })?;
+        instructions.push(instruction);
+    }
+    if instructions.is_empty() {
+        return Err(ParseError::NoInstructions);
+    }
 
-    let instructions: Vec<Instruction> = instructions_input.chars().map(|c| Instruction::from_str(&c.to_string()).unwrap()).collect::<Vec<Instruction>>();
-    let mut nodes: HashMap<String, (String, String)> = HashMap::new();
-    for node_input in nodes_input.lines() {
+    let first_node_line = instructions_input.lines().count() + 2;
+    let mut definitions: Vec<(usize, &str, &str, &str)> = Vec::new();
+    for (index, node_input) in nodes_input.lines().enumerate() {
+        let line = first_node_line + index;
+        let malformed = ParseError::MalformedNode { line };
         let node = node_input.split(" = ").collect::<Vec<&str>>();
-        let [key, directions_input] = <[&str; 2]>::try_from(node).ok().unwrap();
+        let Ok([key, directions_input]) = <[&str; 2]>::try_from(node) else { return Err(malformed) };
+        let Some(directions_input) = directions_input.strip_prefix('(').and_then(|d| d.strip_suffix(')')) else { return Err(malformed) };
         let directions = directions_input.split(", ").collect::<Vec<&str>>();
-        let [left, right] = <[&str; 2]>::try_from(directions).ok().unwrap();
-        nodes.insert(key.to_string(), (left[1..].to_string(), right[..right.len() - 1].to_string()));
+        let Ok([left, right]) = <[&str; 2]>::try_from(directions) else { return Err(malformed) };
+        definitions.push((line, key, left, right));
+    }
+
+    let mut names: Vec<String> = Vec::new();
+    let mut ids: HashMap<String, NodeId> = HashMap::new();
+    for (_, key, _, _) in &definitions {
+        if !ids.contains_key(*key) {
+            ids.insert(key.to_string(), names.len() as NodeId);
+            names.push(key.to_string());
+        }
+    }
+
+    let mut left: Vec<NodeId> = vec![0; names.len()];
+    let mut right: Vec<NodeId> = vec![0; names.len()];
+    for (line, key, left_name, right_name) in definitions {
+        let resolve = |name: &str| ids.get(name).copied().ok_or(ParseError::UndefinedNode { line, node: name.to_string() });
+        let id = ids[key] as usize;
+        left[id] = resolve(left_name)?;
+        right[id] = resolve(right_name)?;
     }
 
-    return Network { instructions, nodes };
+    return Ok(Network { instructions, names, ids, left, right });
 }
 
-fn puzzle_1() {
-    let network = parse_input();
-    let destination = "ZZZ";
-    let mut current_position = "AAA";
-    let mut steps = 0;
-
-    while current_position != destination {
-        for instruction in &network.instructions {
-            let (left, right) = network.nodes.get(current_position).unwrap();
-            match instruction {
-                Instruction::Left => current_position = left,
-                Instruction::Right => current_position = right
-            }
-            steps += 1;
+/// Selects nodes by name: exactly, by suffix, or by a glob where `?` matches
+/// any single character and `*` any run of characters.
+#[derive(Debug, Clone, PartialEq)]
+enum NodePattern {
+    Exact(String),
+    Suffix(String),
+    Glob(String),
+}
+
+impl NodePattern {
+    fn matches(&self, name: &str) -> bool {
+        return match self {
+            NodePattern::Exact(exact) => name == exact,
+            NodePattern::Suffix(suffix) => name.ends_with(suffix.as_str()),
+            NodePattern::Glob(glob) => glob_matches(&glob.chars().collect::<Vec<char>>(), &name.chars().collect::<Vec<char>>()),
+        };
+    }
+}
+
+fn glob_matches(glob: &[char], name: &[char]) -> bool {
+    return match glob.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| glob_matches(rest, &name[skip..])),
+        Some((&c, rest)) => name.split_first().is_some_and(|(&n, name)| (c == '?' || c == n) && glob_matches(rest, name)),
+    };
+}
+
+impl FromStr for NodePattern {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(());
+        }
+        if s.contains(['*', '?']) {
+            return Ok(NodePattern::Glob(s.to_string()));
+        }
+        return Ok(NodePattern::Exact(s.to_string()));
+    }
+}
+
+impl fmt::Display for NodePattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NodePattern::Exact(exact) => write!(f, "{exact}"),
+            NodePattern::Suffix(suffix) => write!(f, "*{suffix}"),
+            NodePattern::Glob(glob) => write!(f, "{glob}"),
         }
     }
+}
 
-    println!("{:?}", steps)
+#[derive(Debug, PartialEq)]
+enum WalkError {
+    NoMatchingNode(String),
+    /// The walk revisited a (node, instruction index) state without passing an end node.
+    Unreachable { from: String, to: String, steps: u64 },
 }
 
-fn puzzle_2() {
-    let network = parse_input();
-    let current_nodes: &Vec<String> = &mut network.nodes.iter().map(|node| node.0.clone()).filter(|node| node.ends_with("A")).collect::<Vec<String>>();
-    let mut steps: Vec<i64> = Vec::new();
+impl fmt::Display for WalkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WalkError::NoMatchingNode(pattern) => write!(f, "no node in the network matches {pattern}"),
+            WalkError::Unreachable { from, to, steps } => write!(f, "{to} is unreachable from {from}: the walk repeats itself within {steps} steps"),
+        }
+    }
+}
 
-    println!("{:?}", current_nodes);
+/// Steps from `start` until the first node matching `ends`, following
+/// `instructions` over and over.
+fn walk(network: &Network, instructions: &[Instruction], start: NodeId, ends: &NodePattern) -> Result<u64, WalkError> {
+    // There are only this many (node, instruction index) states, so a walk
+    // that takes longer has repeated one and will never get anywhere new.
+    let limit = network.names.len() as u64 * instructions.len() as u64;
+    let mut current_position = start;
+    let mut steps: u64 = 0;
 
-    for node in current_nodes {
-        let mut count = 0;
-        let mut current_node = node;
-        for instruction in network.instructions.iter().cycle() {
-            if current_node.ends_with("Z") {
-                break;
-            }
-            let (left, right) = &network.nodes.get(current_node).unwrap();
-            match instruction {
-                Instruction::Left => current_node = &left,
-                Instruction::Right => current_node = &right
+    while !ends.matches(network.name(current_position)) {
+        if steps >= limit {
+            return Err(WalkError::Unreachable { from: network.name(start).to_string(), to: ends.to_string(), steps });
+        }
+        let instruction = &instructions[steps as usize % instructions.len()];
+        current_position = network.next(current_position, instruction);
+        steps += 1;
+    }
+    return Ok(steps);
+}
+
+fn matching_nodes(network: &Network, pattern: &NodePattern) -> Result<Vec<NodeId>, WalkError> {
+    let nodes: Vec<NodeId> = network.node_ids().filter(|&node| pattern.matches(network.name(node))).collect();
+    if nodes.is_empty() {
+        return Err(WalkError::NoMatchingNode(pattern.to_string()));
+    }
+    return Ok(nodes);
+}
+
+/// Steps taken from each start node, by name.
+type Walks<'a> = Vec<(&'a str, Result<u64, WalkError>)>;
+
+/// Walks from every node matching `starts` to the first node matching `ends`.
+fn walk_from<'a>(network: &'a Network, starts: &NodePattern, ends: &NodePattern) -> Result<Walks<'a>, WalkError> {
+    matching_nodes(network, ends)?;
+    return Ok(matching_nodes(network, starts)?.into_iter()
+        .map(|start| (network.name(start), walk(network, &network.instructions, start, ends)))
+        .collect());
+}
+
+fn parse_input() -> Network {
+    let content = fs::read_to_string("src/day8/input.txt").expect("Something went wrong reading the file");
+    return parse_network(&content).unwrap_or_else(|error| panic!("{error}"));
+}
+
+fn print_walks(network: &Network, starts: &NodePattern, ends: &NodePattern) {
+    match walk_from(network, starts, ends) {
+        Ok(walks) => for (start, steps) in walks {
+            match steps {
+                Ok(steps) => println!("{start}: {steps}"),
+                Err(error) => println!("{start}: {error}")
             }
-            count += 1;
         }
-        steps.push(count);
+        Err(error) => println!("{error}")
     }
-    let gcd = network.instructions.len() as i64;
-    let mut lcm = (steps.pop().unwrap() * steps.pop().unwrap()) / gcd;
-    while steps.len() > 0 {
-        lcm = (lcm * steps.pop().unwrap()) / gcd;
+}
+
+fn puzzle_1() {
+    let network = parse_input();
+    let ends = NodePattern::Exact(String::from("ZZZ"));
+    let steps = matching_nodes(&network, &ends)
+        .and_then(|_| network.id("AAA").ok_or(WalkError::NoMatchingNode(String::from("AAA"))))
+        .and_then(|start| walk(&network, &network.instructions, start, &ends));
+
+    match steps {
+        Ok(steps) => println!("{steps}"),
+        Err(error) => println!("{error}")
     }
+}
+
+fn puzzle_2() {
+    let network = parse_input();
+    let (starts, ends) = (NodePattern::Suffix(String::from("A")), NodePattern::Suffix(String::from("Z")));
 
-    println!("{lcm}")
+    match cycles::synchronised_steps(&network, &starts, &ends) {
+        Ok(Some(steps)) => println!("{steps}"),
+        Ok(None) => println!("Ghosts never synchronise"),
+        Err(error) => println!("{error}")
+    }
 }
 
 pub(crate) fn run() {
     // puzzle_1();
     puzzle_2();
+    // shortest::run(&parse_input());
+    // jumps::run(&parse_input(), 1_000_000_000_000_000);
+    // lockstep::run(&parse_input(), 1_000_000_000, "day8.checkpoint");
+    // dot::export(&parse_input(), "day8.dot", Some(("AAA", 11309)));
+    // print_walks(&parse_input(), &NodePattern::Glob(String::from("??A")), &NodePattern::Suffix(String::from("Z")));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exact(name: &str) -> NodePattern {
+        NodePattern::Exact(name.to_string())
+    }
+
+    #[test]
+    fn walk_reaches_destination() {
+        let network = parse_network("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        assert_eq!(walk_from(&network, &exact("AAA"), &exact("ZZZ")), Ok(vec![("AAA", Ok(6))]));
+    }
+
+    #[test]
+    fn walk_detects_unreachable_destination() {
+        let network = parse_network("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        let unreachable = WalkError::Unreachable { from: String::from("AAA"), to: String::from("ZZZ"), steps: 3 * 2 };
+        assert_eq!(walk_from(&network, &exact("AAA"), &exact("ZZZ")), Ok(vec![("AAA", Err(unreachable))]));
+        assert_eq!(walk_from(&network, &exact("AAA"), &exact("YYY")), Err(WalkError::NoMatchingNode(String::from("YYY"))));
+    }
+
+    #[test]
+    fn walks_from_every_matching_start() {
+        let network = parse_network("L\n\n11A = (11Z, 11Z)\n22A = (22B, 22B)\n22B = (22Z, 22Z)\n11Z = (11Z, 11Z)\n22Z = (22Z, 22Z)\n").unwrap();
+        let walks = walk_from(&network, &NodePattern::from_str("??A").unwrap(), &NodePattern::Suffix(String::from("Z"))).unwrap();
+        assert_eq!(walks, vec![("11A", Ok(1)), ("22A", Ok(2))]);
+        let walks = walk_from(&network, &NodePattern::from_str("2*").unwrap(), &NodePattern::from_str("*2Z").unwrap()).unwrap();
+        assert_eq!(walks, vec![("22A", Ok(2)), ("22B", Ok(1)), ("22Z", Ok(0))]);
+    }
+
+    #[test]
+    fn dangling_references_are_rejected() {
+        let error = parse_network("L\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap_err();
+        assert_eq!(error, ParseError::UndefinedNode { line: 3, node: String::from("BBB") });
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_offending_input() {
+        assert_eq!(parse_network("LR\nAAA = (AAA, AAA)\n").err(), Some(ParseError::MissingSection));
+        assert_eq!(parse_network("\n\nAAA = (AAA, AAA)\n").err(), Some(ParseError::NoInstructions));
+        assert_eq!(parse_network("LRXL\n\nAAA = (AAA, AAA)\n").err(), Some(ParseError::InvalidInstruction { line: 1, column: 3, instruction: 'X' }));
+        assert_eq!(parse_network("LR\n\nAAA = (AAA, AAA)\nBBB = AAA, AAA\n").err(), Some(ParseError::MalformedNode { line: 4 }));
+        assert_eq!(parse_network("LR\n\nAAA = (AAA, AAA)\nBBB (AAA, AAA)\n").err(), Some(ParseError::MalformedNode { line: 4 }));
+        assert_eq!(parse_network("LR\n\nAAA = (AAA AAA)\n").err(), Some(ParseError::MalformedNode { line: 3 }));
+        assert_eq!(ParseError::InvalidInstruction { line: 1, column: 3, instruction: 'X' }.to_string(), "line 1, column 3: invalid instruction 'X'");
+    }
 }
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+
+use super::{matching_nodes, walk, Instruction, Network, NodeId, NodePattern, WalkError};
+
+/// Shortest instruction sequence leading from any node matching `starts` to any
+/// node matching `ends`, together with the start it begins at. `None` if no end
+/// node is reachable at all.
+fn shortest_path(network: &Network, starts: &NodePattern, ends: &NodePattern) -> Result<Option<(NodeId, Vec<Instruction>)>, WalkError> {
+    // The node and instruction each node was first reached from.
+    let mut reached_from: Vec<Option<(NodeId, Instruction)>> = vec![None; network.names.len()];
+    let mut visited: Vec<bool> = vec![false; network.names.len()];
+    let mut queue: VecDeque<NodeId> = VecDeque::new();
+    for start in matching_nodes(network, starts)? {
+        visited[start as usize] = true;
+        queue.push_back(start);
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if ends.matches(network.name(node)) {
+            let mut instructions: Vec<Instruction> = Vec::new();
+            let mut current = node;
+            while let Some((previous, instruction)) = reached_from[current as usize] {
+                instructions.push(instruction);
+                current = previous;
+            }
+            instructions.reverse();
+            return Ok(Some((current, instructions)));
+        }
+        for instruction in [Instruction::Left, Instruction::Right] {
+            let next = network.next(node, &instruction);
+            if !visited[next as usize] {
+                visited[next as usize] = true;
+                reached_from[next as usize] = Some((node, instruction));
+                queue.push_back(next);
+            }
+        }
+    }
+    return Ok(None);
+}
+
+fn spell(instructions: &[Instruction]) -> String {
+    return instructions.iter().map(|instruction| match instruction {
+        Instruction::Left => 'L',
+        Instruction::Right => 'R'
+    }).collect();
+}
+
+#[derive(Debug, PartialEq)]
+enum Optimality {
+    Optimal(u64),
+    Suboptimal { walked: u64, shortest: u64 },
+}
+
+/// Whether following `instructions` from `start` reaches `ends` in as few steps as possible.
+fn is_optimal(network: &Network, instructions: &[Instruction], start: &str, ends: &NodePattern) -> Result<Optimality, WalkError> {
+    let starts = NodePattern::Exact(start.to_string());
+    let start_id = matching_nodes(network, &starts)?[0];
+    let walked = walk(network, instructions, start_id, ends)?;
+    let shortest = shortest_path(network, &starts, ends)?.map(|(_, path)| path.len() as u64)
+        .expect("a finished walk implies a path");
+    return Ok(if walked == shortest { Optimality::Optimal(walked) } else { Optimality::Suboptimal { walked, shortest } });
+}
+
+pub(super) fn run(network: &Network) {
+    let queries = [
+        (NodePattern::Exact(String::from("AAA")), NodePattern::Exact(String::from("ZZZ"))),
+        (NodePattern::Suffix(String::from("A")), NodePattern::Suffix(String::from("Z"))),
+    ];
+    for (starts, ends) in queries {
+        match shortest_path(network, &starts, &ends) {
+            Ok(Some((start, path))) => println!("{starts} -> {ends}: {} from {} ({} steps)", spell(&path), network.name(start), path.len()),
+            Ok(None) => println!("{starts} -> {ends}: unreachable"),
+            Err(error) => println!("{error}")
+        }
+    }
+
+    match is_optimal(network, &network.instructions, "AAA", &NodePattern::Exact(String::from("ZZZ"))) {
+        Ok(Optimality::Optimal(steps)) => println!("Instructions reach ZZZ in {steps} steps, which is optimal"),
+        Ok(Optimality::Suboptimal { walked, shortest }) => println!("Instructions reach ZZZ in {walked} steps, {shortest} would do"),
+        Err(error) => println!("{error}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::day8::parse_network;
+
+    #[test]
+    fn shortest_instruction_string() {
+        let network = parse_network("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        let (start, path) = shortest_path(&network, &NodePattern::Exact(String::from("AAA")), &NodePattern::Exact(String::from("ZZZ"))).unwrap().unwrap();
+        assert_eq!((network.name(start), spell(&path).as_str()), ("AAA", "LR"));
+    }
+
+    #[test]
+    fn optimality_of_given_instructions() {
+        let network = parse_network("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        let ends = NodePattern::Exact(String::from("ZZZ"));
+        let instructions = |spelling: &str| spelling.chars().map(|c| Instruction::from_str(&c.to_string()).unwrap()).collect::<Vec<Instruction>>();
+        assert_eq!(is_optimal(&network, &instructions("LR"), "AAA", &ends), Ok(Optimality::Optimal(2)));
+        assert_eq!(is_optimal(&network, &instructions("RRLR"), "AAA", &ends), Ok(Optimality::Optimal(2)));
+        assert_eq!(is_optimal(&network, &network.instructions, "AAA", &ends), Ok(Optimality::Suboptimal { walked: 6, shortest: 2 }));
+        assert_eq!(is_optimal(&network, &instructions("L"), "AAA", &ends), Err(WalkError::Unreachable { from: String::from("AAA"), to: String::from("ZZZ"), steps: 3 }));
+    }
+
+    #[test]
+    fn shortest_from_any_start() {
+        let network = parse_network("L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n22A = (22Z, 22Z)\n11Z = (11Z, 11Z)\n22Z = (22Z, 22Z)\nXXX = (XXX, XXX)\n").unwrap();
+        let (start, path) = shortest_path(&network, &NodePattern::Suffix(String::from("A")), &NodePattern::Suffix(String::from("Z"))).unwrap().unwrap();
+        assert_eq!((network.name(start), spell(&path).as_str()), ("22A", "L"));
+        assert_eq!(shortest_path(&network, &NodePattern::Exact(String::from("XXX")), &NodePattern::Suffix(String::from("Z"))), Ok(None));
+    }
+}
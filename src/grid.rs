@@ -0,0 +1,153 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::ops::{Index, IndexMut};
+
+/// (row, column) of a cell.
+pub(crate) type Position = (usize, usize);
+
+/// Row and column offsets of the 4 orthogonal neighbours.
+pub(crate) const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Row and column offsets of all 8 neighbours, diagonals included.
+pub(crate) const ADJACENT: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+/// Rectangular grid stored row by row in a single vector.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub(crate) fn new(width: usize, height: usize, fill: T) -> Grid<T> where T: Clone {
+        return Grid { width, height, cells: vec![fill; width * height] };
+    }
+
+    /// Fails if the rows are not all the same length.
+    pub(crate) fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, String> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some(index) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!("row {} has {} cells, expected {width}", index + 1, rows[index].len()));
+        }
+        let height = rows.len();
+        return Ok(Grid { width, height, cells: rows.into_iter().flatten().collect() });
+    }
+
+    pub(crate) fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub(crate) fn contains(&self, (row, column): Position) -> bool {
+        return row < self.height && column < self.width;
+    }
+
+    pub(crate) fn get(&self, position: Position) -> Option<&T> {
+        return if self.contains(position) { Some(&self.cells[position.0 * self.width + position.1]) } else { None };
+    }
+
+    pub(crate) fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        let width = self.width;
+        return if self.contains(position) { Some(&mut self.cells[position.0 * width + position.1]) } else { None };
+    }
+
+    /// `position` moved by `(rows, columns)`, `None` if that leaves the grid.
+    pub(crate) fn offset(&self, (row, column): Position, (rows, columns): (isize, isize)) -> Option<Position> {
+        let position = (row.checked_add_signed(rows)?, column.checked_add_signed(columns)?);
+        return if self.contains(position) { Some(position) } else { None };
+    }
+
+    /// Positions at `offsets` from `position` that lie within the grid.
+    pub(crate) fn neighbours<'a>(&'a self, position: Position, offsets: &'a [(isize, isize)]) -> impl Iterator<Item=Position> + 'a {
+        return offsets.iter().filter_map(move |&offset| self.offset(position, offset));
+    }
+
+    pub(crate) fn positions(&self) -> impl Iterator<Item=Position> {
+        let width = self.width;
+        return (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)));
+    }
+
+    pub(crate) fn row(&self, row: usize) -> &[T] {
+        return &self.cells[row * self.width..(row + 1) * self.width];
+    }
+
+    pub(crate) fn rows(&self) -> impl Iterator<Item=&[T]> {
+        return (0..self.height).map(|row| self.row(row));
+    }
+
+    pub(crate) fn column(&self, column: usize) -> impl Iterator<Item=&T> {
+        return self.cells.iter().skip(column).step_by(self.width.max(1)).take(if column < self.width { self.height } else { 0 });
+    }
+
+    pub(crate) fn columns(&self) -> impl Iterator<Item=impl Iterator<Item=&T>> {
+        return (0..self.width).map(|column| self.column(column));
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        return self.get(position).unwrap_or_else(|| panic!("{position:?} is outside the {}x{} grid", self.width, self.height));
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        return self.get_mut(position).unwrap_or_else(|| panic!("{position:?} is outside the {width}x{height} grid"));
+    }
+}
+
+/// One line per row, cells written back to back.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        return Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]).unwrap();
+    }
+
+    #[test]
+    fn rows_columns_and_display() {
+        let mut grid = letters();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.rows().collect::<Vec<&[char]>>(), vec![&['a', 'b', 'c'][..], &['d', 'e', 'f'][..]]);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.columns().map(|column| column.collect::<String>()).collect::<Vec<String>>(), vec!["ad", "be", "cf"]);
+        grid[(0, 0)] = 'z';
+        assert_eq!(grid.to_string(), "zbc\ndef\n");
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+        assert_eq!(Grid::new(2, 1, 0).to_string(), "00\n");
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = letters();
+        assert_eq!(grid.neighbours((0, 0), &ADJACENT).collect::<Vec<Position>>(), vec![(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(grid.neighbours((1, 1), &ORTHOGONAL).collect::<Vec<Position>>(), vec![(0, 1), (1, 0), (1, 2)]);
+        assert_eq!(grid.neighbours((1, 1), &ADJACENT).count(), 5);
+        assert_eq!(grid.offset((0, 2), (0, 1)), None);
+        assert_eq!(grid.offset((1, 2), (-1, -2)), Some((0, 0)));
+        assert_eq!(grid.positions().count(), 6);
+    }
+}
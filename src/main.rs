@@ -1,3 +1,6 @@
+use std::{env, process};
+
+mod cli;
 mod day1;
 mod day2;
 mod day3;
@@ -6,8 +9,27 @@ mod day5;
 mod day6;
 mod day7;
 mod day8;
+mod grid;
+mod math;
+mod verbosity;
 
 fn main() {
+    let options = cli::parse(env::args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        process::exit(2);
+    });
+    verbosity::set_verbosity(options.verbosity);
+    if let Some(log_file) = &options.log_file {
+        verbosity::set_log_file(log_file).unwrap_or_else(|error| {
+            eprintln!("{log_file}: {error}");
+            process::exit(2);
+        });
+    }
+    if let Some(bag) = &options.bag {
+        day2::check_bag(bag);
+        return;
+    }
+
     // day1::run();
     // day2::run();
     // day3::run();
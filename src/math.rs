@@ -0,0 +1,140 @@
+use std::ops::{Div, Rem};
+
+/// Unsigned integers the number theory helpers below work on.
+pub(crate) trait Unsigned: Copy + PartialEq + PartialOrd + Div<Output=Self> + Rem<Output=Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(impl Unsigned for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                return <$t>::checked_mul(self, other);
+            }
+        })*
+    };
+}
+
+impl_unsigned!(u32, u64, u128, usize);
+
+pub(crate) fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    return a;
+}
+
+/// Least common multiple, `None` if it does not fit in `T`.
+pub(crate) fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    return (a / gcd(a, b)).checked_mul(b);
+}
+
+/// Least common multiple of all `values` (1 for none), `None` on overflow.
+pub(crate) fn lcm_all<T: Unsigned>(values: impl IntoIterator<Item=T>) -> Option<T> {
+    let mut result = T::ONE;
+    for value in values {
+        result = lcm(result, value)?;
+    }
+    return Some(result);
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum CrtError {
+    /// The congruences have no common solution.
+    Incompatible,
+    Overflow,
+}
+
+/// `a * b mod modulus` without overflowing for any `u128` operands.
+fn mul_mod(mut a: u128, mut b: u128, modulus: u128) -> u128 {
+    let mut result = 0;
+    a %= modulus;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b >>= 1;
+    }
+    return result;
+}
+
+fn add_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    return if a >= modulus - b { a - (modulus - b) } else { a + b };
+}
+
+/// Inverse of `a` modulo `modulus`, both coprime.
+fn mod_inverse(a: u128, modulus: u128) -> Result<u128, CrtError> {
+    let modulus_signed = i128::try_from(modulus).map_err(|_| CrtError::Overflow)?;
+    let (mut old_r, mut r) = (i128::try_from(a).map_err(|_| CrtError::Overflow)?, modulus_signed);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+    }
+    return Ok(old_s.rem_euclid(modulus_signed) as u128);
+}
+
+/// Merges `x ≡ a.0 (mod a.1)` and `x ≡ b.0 (mod b.1)` into a single congruence
+/// `x ≡ r (mod lcm(a.1, b.1))`. The moduli do not need to be coprime.
+pub(crate) fn crt(a: (u128, u128), b: (u128, u128)) -> Result<(u128, u128), CrtError> {
+    let (r1, m1) = (a.0 % a.1, a.1);
+    let (r2, m2) = (b.0 % b.1, b.1);
+    let g = gcd(m1, m2);
+    let difference = add_mod(r2, m2 - r1 % m2, m2);
+    if !difference.is_multiple_of(g) {
+        return Err(CrtError::Incompatible);
+    }
+
+    let reduced = m2 / g;
+    let k = mul_mod(difference / g, mod_inverse((m1 / g) % reduced, reduced)?, reduced);
+    let modulus = (m1 / g).checked_mul(m2).ok_or(CrtError::Overflow)?;
+    // r1 < m1 and k < m2 / g, so this stays below the combined modulus.
+    return Ok((r1 + m1 * k, modulus));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_of_coprime_and_shared_factors() {
+        assert_eq!(gcd(17u64, 5), 1);
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(0u64, 7), 7);
+        assert_eq!(gcd(7u128, 0), 7);
+    }
+
+    #[test]
+    fn lcm_does_not_assume_a_common_factor() {
+        assert_eq!(lcm(4u64, 6), Some(12));
+        assert_eq!(lcm_all([3u64, 5, 7]), Some(105));
+        assert_eq!(lcm_all([263u64 * 2, 263 * 3, 263 * 4]), Some(263 * 12));
+        assert_eq!(lcm_all(Vec::<u64>::new()), Some(1));
+    }
+
+    #[test]
+    fn lcm_reports_overflow() {
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(lcm(u64::MAX as u128, u64::MAX as u128 - 1), Some(u64::MAX as u128 * (u64::MAX as u128 - 1)));
+    }
+
+    #[test]
+    fn crt_with_coprime_and_shared_moduli() {
+        assert_eq!(crt((2, 3), (3, 5)), Ok((8, 15)));
+        assert_eq!(crt((2, 4), (4, 6)), Ok((10, 12)));
+        assert_eq!(crt((0, 1), (5, 7)), Ok((5, 7)));
+        assert_eq!(crt((1, 4), (2, 6)), Err(CrtError::Incompatible));
+        assert_eq!(crt((1, u128::MAX), (0, u128::MAX - 1)), Err(CrtError::Overflow));
+    }
+}
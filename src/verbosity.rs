@@ -0,0 +1,79 @@
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::Write;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// How much the puzzles print besides their answers: nothing (`-q`), progress
+/// and summaries, or additionally a trace of intermediate results (`-v`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Verbosity {
+    Quiet,
+    #[default]
+    Normal,
+    Trace,
+}
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+/// Where traces go instead of stderr, if set.
+static LOG_FILE: Mutex<Option<File>> = Mutex::new(None);
+
+pub(crate) fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+pub(crate) fn verbosity() -> Verbosity {
+    return match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        1 => Verbosity::Normal,
+        _ => Verbosity::Trace,
+    };
+}
+
+pub(crate) fn set_log_file(path: &str) -> io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    *LOG_FILE.lock().unwrap() = Some(file);
+    return Ok(());
+}
+
+pub(crate) fn write_trace(args: fmt::Arguments) {
+    match LOG_FILE.lock().unwrap().as_mut() {
+        Some(file) => writeln!(file, "{args}").expect("Something went wrong writing the log file"),
+        None => eprintln!("{args}")
+    }
+}
+
+/// Prints intermediate results when running with `-v`.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::verbosity::verbosity() >= $crate::verbosity::Verbosity::Trace {
+            $crate::verbosity::write_trace(format_args!($($arg)*));
+        }
+    };
+}
+
+/// Prints supplementary output unless running with `-q`.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::verbosity::verbosity() >= $crate::verbosity::Verbosity::Normal {
+            println!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use info;
+pub(crate) use trace;
+
+/// Progress bar in the shared style, hidden when running with `-q`.
+pub(crate) fn progress_bar(len: u64) -> ProgressBar {
+    if verbosity() == Verbosity::Quiet {
+        return ProgressBar::hidden();
+    }
+    let pb = ProgressBar::new(len);
+    pb.set_style(ProgressStyle::with_template("[{elapsed_precise}] {wide_bar} {pos:>7}/{len:7} ({percent}%) remaining: ~{eta} {msg}")
+        .unwrap());
+    return pb;
+}